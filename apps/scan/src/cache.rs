@@ -0,0 +1,174 @@
+//! On-disk cache of parse results.
+//!
+//! The cache directory holds an `index.json` that maps every input file to the
+//! hash of its content and the output it was written to, plus the serialized
+//! ASTs under `ast/` when a combined document is requested. The index is keyed
+//! by the rico version, so upgrading the parser invalidates all entries.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use colored::Colorize;
+use rico::ast::Document;
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_128;
+
+use crate::rules::Rule;
+
+/// Cached state of a single input file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Hash of the file content
+    pub hash: String,
+    /// JSON output path the AST was written to
+    pub output: Option<PathBuf>,
+    /// Whether the JSON output is pretty printed
+    pub pretty: bool,
+    /// Validation rules the file passed
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    /// Whether the file passed in strict mode
+    #[serde(default)]
+    pub strict: bool,
+}
+
+impl Entry {
+    /// Creates the entry for a file with the given content and output settings
+    pub fn new(
+        content: &str,
+        output: Option<PathBuf>,
+        pretty: bool,
+        rules: &[Rule],
+        strict: bool,
+    ) -> Self {
+        Self {
+            hash: format!("{:032x}", xxh3_128(content.as_bytes())),
+            output,
+            pretty,
+            rules: rules.to_vec(),
+            strict,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct Index {
+    /// The rico version the entries were produced with
+    version: String,
+    /// Entries keyed by input path
+    files: BTreeMap<PathBuf, Entry>,
+}
+
+/// The parse result cache, shared between worker threads
+pub struct Cache {
+    /// Cache directory
+    dir: PathBuf,
+    /// Entries of the previous run
+    previous: BTreeMap<PathBuf, Entry>,
+    /// Entries confirmed or added in this run
+    current: Mutex<BTreeMap<PathBuf, Entry>>,
+}
+
+impl Cache {
+    /// Opens the cache in `dir`
+    /// A missing, unreadable or outdated index results in an empty cache
+    pub fn open(dir: &Path) -> Self {
+        let previous = fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|json| serde_json::from_str::<Index>(&json).ok())
+            .filter(|index| index.version == rico::VERSION)
+            .map(|index| index.files)
+            .unwrap_or_default();
+
+        Self {
+            dir: dir.to_path_buf(),
+            previous,
+            current: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns true if the file is unchanged since the last run and its
+    /// output, if any, still exists
+    pub fn is_fresh(&self, file: &Path, entry: &Entry) -> bool {
+        let fresh = self.previous.get(file) == Some(entry)
+            && entry.output.as_ref().is_none_or(|output| output.is_file());
+        if fresh {
+            self.current
+                .lock()
+                .unwrap()
+                .insert(file.to_path_buf(), entry.clone());
+        }
+        fresh
+    }
+
+    /// Loads the cached AST of a file content hash
+    pub fn load_ast(&self, hash: &str) -> Option<Document> {
+        let json = fs::read_to_string(self.ast_path(hash)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Records a processed file, storing its AST if given
+    pub fn store(&self, file: &Path, entry: Entry, ast: Option<&Document>) {
+        if let Some(ast) = ast {
+            let path = self.ast_path(&entry.hash);
+            let stored = fs::create_dir_all(self.dir.join("ast"))
+                .ok()
+                .and_then(|_| serde_json::to_string(ast).ok())
+                .and_then(|json| fs::write(&path, json).ok());
+            if stored.is_none() {
+                return;
+            }
+        }
+        self.current
+            .lock()
+            .unwrap()
+            .insert(file.to_path_buf(), entry);
+    }
+
+    /// Writes the index, keeping entries of files that were not part of this
+    /// run as long as they still exist, and removes unreferenced ASTs
+    pub fn save(self) {
+        let mut files = self.current.into_inner().unwrap();
+        for (file, entry) in self.previous {
+            if file.is_file() {
+                files.entry(file).or_insert(entry);
+            }
+        }
+
+        let hashes: HashSet<&str> = files.values().map(|entry| entry.hash.as_str()).collect();
+        if let Ok(entries) = fs::read_dir(self.dir.join("ast")) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let referenced = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|hash| hashes.contains(hash));
+                if !referenced {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
+
+        let index = Index {
+            version: rico::VERSION.to_string(),
+            files,
+        };
+        let result = fs::create_dir_all(&self.dir).and_then(|_| {
+            let json = serde_json::to_string(&index)?;
+            fs::write(self.dir.join("index.json"), json)
+        });
+        if let Err(e) = result {
+            eprintln!(
+                "{} {}",
+                "!".yellow(),
+                format!("Failed to write cache {}: {}", self.dir.display(), e).yellow()
+            );
+        }
+    }
+
+    fn ast_path(&self, hash: &str) -> PathBuf {
+        self.dir.join("ast").join(hash).with_extension("json")
+    }
+}
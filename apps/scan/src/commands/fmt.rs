@@ -0,0 +1,199 @@
+//! `rico-scan fmt`: formats Thrift files in place, or checks that they are formatted.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::Args;
+use miette::{miette, LabeledSpan, NamedSource, Result};
+use rico::format::{format, unified_diff};
+use rico::lexer::{tokenize, Token};
+use rico::writer::WriterOptions;
+
+use super::{InputArgs, WriterArgs};
+use crate::encoding::Encoding;
+use crate::files::{read_input, read_stdin, with_source, write_file};
+use crate::pipeline::{finish, process_files, write_stdout};
+
+/// Arguments of the fmt command
+#[derive(Args, Debug)]
+pub struct FmtArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Only check formatting, exit with a non-zero status if any file would change
+    #[arg(long)]
+    pub check: bool,
+
+    #[command(flatten)]
+    pub writer: WriterArgs,
+}
+
+/// Formats a single Thrift file
+///
+/// Returns the unified diff between the file and its formatted form, which is
+/// empty when the file is already formatted. Unless `check` is set, changed
+/// files are rewritten in place, keeping their encoding. Files whose comments
+/// the writer would drop fail and are left unchanged.
+pub fn process_file(
+    input: &Path,
+    options: &WriterOptions,
+    encoding: Option<&'static Encoding>,
+    check: bool,
+) -> Result<String> {
+    let decoded = read_input(input, encoding)?;
+    let content = &decoded.text;
+    let formatted =
+        format(content, options.clone()).map_err(|error| with_source(error, input, content))?;
+    check_comments(content, &formatted, input)?;
+
+    if formatted == *content {
+        return Ok(String::new());
+    }
+    if !check {
+        write_file(input, decoded.encode(&formatted))?;
+    }
+
+    let label = input.display().to_string();
+    Ok(unified_diff(content, &formatted, &label, &label))
+}
+
+/// Checks that `formatted` keeps every comment of `content`
+///
+/// The writer only keeps comments in front of definitions, fields and members;
+/// comments at the end of a line, before a closing brace or at the end of the
+/// file are dropped. Comments are compared as a multiset, since canonical mode
+/// reorders fields, and line by line without surrounding whitespace, since
+/// block comments are re-indented.
+pub fn check_comments(content: &str, formatted: &str, input: &Path) -> Result<()> {
+    let mut kept: HashMap<String, usize> = HashMap::new();
+    for (text, _) in comments(formatted) {
+        *kept.entry(text).or_default() += 1;
+    }
+    for (text, range) in comments(content) {
+        match kept.get_mut(&text) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => {
+                return Err(miette!(
+                    code = "rico_scan::comment_dropped",
+                    labels = vec![LabeledSpan::at(range, "this comment would be removed")],
+                    help = "move the comment onto its own line above a definition, field or member",
+                    "Formatting {} would remove a comment",
+                    input.display()
+                )
+                .with_source_code(NamedSource::new(
+                    input.display().to_string(),
+                    content.to_string(),
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the normalized text and byte range of every comment in `text`
+fn comments(text: &str) -> Vec<(String, Range<usize>)> {
+    tokenize(text)
+        .filter(|(token, _, _)| matches!(token, Token::LineComment | Token::BlockComment))
+        .map(|(_, comment, loc)| {
+            let lines: Vec<&str> = comment.lines().map(str::trim).collect();
+            (lines.join("\n"), loc.start.index..loc.end.index)
+        })
+        .collect()
+}
+
+/// Formats a single document from stdin and prints it to stdout in the
+/// encoding it was read in
+/// With `--check`, prints the diff instead and fails if the document would change
+/// Errors are rendered to stderr, attributed to `name`
+fn run_stdin(args: &FmtArgs, name: &Path) -> Result<ExitCode> {
+    let decoded = read_stdin(args.input.encoding, name)?;
+    let content = &decoded.text;
+    let formatted = match format(content, args.writer.options()) {
+        Ok(formatted) => formatted,
+        Err(error) => {
+            eprintln!("{:?}", with_source(error, name, content));
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    if let Err(error) = check_comments(content, &formatted, name) {
+        eprintln!("{:?}", error);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    if !args.check {
+        write_stdout(decoded.encode(&formatted))?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if formatted == *content {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let label = name.display().to_string();
+    write_stdout(unified_diff(content, &formatted, &label, &label))?;
+    eprintln!(
+        "{:?}",
+        miette!(
+            code = "rico_scan::unformatted",
+            help = "run `rico-scan fmt` to format this file",
+            "{} is not formatted",
+            label
+        )
+    );
+    Ok(ExitCode::FAILURE)
+}
+
+pub fn run(args: FmtArgs) -> Result<ExitCode> {
+    if let Some(name) = args.input.stdin() {
+        return run_stdin(&args, name);
+    }
+
+    let start_time = Instant::now();
+    let human = args.input.human();
+    let Some(thrift_files) = args.input.discover("thrift", "Thrift")? else {
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let options = args.writer.options();
+    let mut outcome = process_files(&thrift_files, args.input.human(), |file| {
+        process_file(file, &options, args.input.encoding, args.check)
+    });
+
+    let changed: Vec<_> = outcome
+        .succeeded
+        .iter()
+        .filter(|(_, diff)| !diff.is_empty())
+        .collect();
+
+    if args.check {
+        for (file, diff) in changed {
+            if human {
+                write_stdout(diff)?;
+            }
+            outcome.failed.push((
+                file,
+                miette!(
+                    code = "rico_scan::unformatted",
+                    help = "run `rico-scan fmt` to format this file",
+                    "{} is not formatted",
+                    file.display()
+                ),
+            ));
+        }
+    } else if human {
+        for (file, _) in &changed {
+            write_stdout(format!("✏️  Formatted {}\n", file.display()))?;
+        }
+    }
+
+    Ok(finish(
+        &thrift_files,
+        outcome.failed,
+        Vec::new(),
+        None,
+        args.input.format,
+        start_time,
+    ))
+}
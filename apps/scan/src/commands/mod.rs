@@ -0,0 +1,276 @@
+//! The rico-scan subcommands.
+//!
+//! Each command discovers its input files, runs the per-file work through the
+//! shared [`pipeline`](crate::pipeline) and reports the outcome.
+
+pub mod fmt;
+pub mod parse;
+pub mod stats;
+pub mod to_thrift;
+
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use miette::{miette, Result};
+use rico::writer::WriterOptions;
+
+use crate::config::{FormatConfig, InputConfig};
+use crate::encoding::{parse_label, Encoding};
+use crate::files::{collect_files, WalkOptions, STDIN};
+use crate::pipeline::write_stdout;
+use crate::report::ReportFormat;
+
+/// Input and report arguments shared by all commands
+///
+/// `F` is the type of `--format`, the scan report for most commands.
+#[derive(Args, Debug)]
+pub struct InputArgs<F: OutputFormat = ReportFormat> {
+    /// Directory or file paths to process
+    /// Defaults to `input.paths` in `rico.toml`
+    #[arg(short = 'p', long = "path", value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Paths to process, in addition to `--path`
+    /// `-` reads a single document from stdin and writes the result to stdout
+    #[arg(value_name = "PATH")]
+    pub files: Vec<PathBuf>,
+
+    /// File name shown in diagnostics when reading from stdin
+    #[arg(long, value_name = "NAME", default_value = "<stdin>")]
+    pub stdin_filename: PathBuf,
+
+    /// Format of the output written to stdout
+    /// Machine-readable formats replace the human-readable output
+    #[arg(long, value_enum, default_value = F::DEFAULT)]
+    pub format: F,
+
+    /// Only process files matching these glob patterns, relative to `--path`
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files and directories matching these glob patterns, relative to `--path`
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// File extensions to process, without the leading dot
+    /// Defaults to `thrift`, or `json` for `to-thrift`
+    #[arg(long = "extension", value_name = "EXT")]
+    pub extensions: Vec<String>,
+
+    /// Do not respect `.gitignore` and `.ignore` files
+    /// Defaults to `input.respect_ignore_files` in `rico.toml`
+    #[arg(long, overrides_with = "respect_ignore")]
+    pub no_ignore: bool,
+
+    /// Respect `.gitignore` and `.ignore` files, the default
+    #[arg(long, overrides_with = "no_ignore")]
+    pub respect_ignore: bool,
+
+    /// Encoding of files without a byte order mark, e.g. `gbk` or `latin1`
+    /// Defaults to UTF-8, or a guess from the content if a file is not valid UTF-8
+    #[arg(long, value_name = "LABEL", value_parser = parse_label)]
+    pub encoding: Option<&'static Encoding>,
+}
+
+impl<F: OutputFormat> InputArgs<F> {
+    /// Fills options that were not given on the command line from the configuration
+    pub fn merge(&mut self, config: &InputConfig) -> Result<()> {
+        self.paths.append(&mut self.files);
+        if self.paths.len() > 1 && self.paths.iter().any(|path| path == Path::new(STDIN)) {
+            return Err(miette!("`-` cannot be combined with other paths"));
+        }
+        if self.paths.is_empty() {
+            self.paths = config.paths.clone();
+        }
+        if self.paths.is_empty() {
+            return Err(miette!(
+                help = "pass --path or set `input.paths` in rico.toml",
+                "No input path given"
+            ));
+        }
+        if self.include.is_empty() {
+            self.include = config.include.clone();
+        }
+        if self.exclude.is_empty() {
+            self.exclude = config.exclude.clone();
+        }
+        if self.extensions.is_empty() {
+            self.extensions = config.extensions.clone();
+        }
+        self.no_ignore = !resolve_flag(
+            self.respect_ignore,
+            self.no_ignore,
+            config.respect_ignore_files,
+            true,
+        );
+        self.respect_ignore = !self.no_ignore;
+        if self.encoding.is_none() {
+            if let Some(label) = &config.encoding {
+                self.encoding = Some(parse_label(label).map_err(|e| {
+                    miette!(
+                        help = "set `input.encoding` in rico.toml to a valid label",
+                        "{}",
+                        e
+                    )
+                })?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the file name to show in diagnostics if the input is read from stdin
+    pub fn stdin(&self) -> Option<&Path> {
+        (self.paths == [Path::new(STDIN)]).then_some(self.stdin_filename.as_path())
+    }
+
+    /// Returns true when the human-readable output is enabled
+    pub fn human(&self) -> bool {
+        self.format.is_human()
+    }
+
+    /// Collects the input files without announcing them
+    /// `extension` is used when no `--extension` is given
+    pub fn collect(&self, extension: &str) -> Result<Vec<PathBuf>> {
+        if self.stdin().is_some() {
+            return Err(miette!(
+                help = "only `parse` and `fmt` read from stdin",
+                "This command cannot read from stdin"
+            ));
+        }
+        let extensions = if self.extensions.is_empty() {
+            vec![extension.to_string()]
+        } else {
+            self.extensions.clone()
+        };
+        let options = WalkOptions {
+            extensions,
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            respect_ignore_files: !self.no_ignore,
+        };
+        let mut files = Vec::new();
+        for path in &self.paths {
+            files.extend(collect_files(path, &options)?);
+        }
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Collects the input files and announces them
+    /// Returns `None` if no files were found
+    pub fn discover(&self, extension: &str, kind: &str) -> Result<Option<Vec<PathBuf>>> {
+        let files = self
+            .collect(extension)
+            .map_err(|e| e.wrap_err(format!("Failed to collect {} files", kind)))?;
+
+        if files.is_empty() && self.human() {
+            write_stdout(format!(
+                "{} {} {}\n",
+                "!".yellow(),
+                format!("No {} files found in", kind).yellow(),
+                self.display_paths().yellow().underline()
+            ))?;
+            return Ok(None);
+        }
+
+        if self.human() {
+            write_stdout(format!("📝 Found {} {} files\n", files.len(), kind))?;
+        }
+        Ok(Some(files))
+    }
+
+    /// Returns the input paths for display
+    pub fn display_paths(&self) -> String {
+        self.paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A format selected with `--format`
+pub trait OutputFormat: ValueEnum + Clone + Debug + Send + Sync + 'static {
+    /// The name of the default value
+    const DEFAULT: &'static str;
+
+    /// Returns true for the human-readable output
+    fn is_human(&self) -> bool;
+}
+
+impl OutputFormat for ReportFormat {
+    const DEFAULT: &'static str = "human";
+
+    fn is_human(&self) -> bool {
+        *self == ReportFormat::Human
+    }
+}
+
+/// Writer options shared by the commands that produce Thrift IDL
+#[derive(Args, Debug)]
+pub struct WriterArgs {
+    /// Maximum line width before lists and parameters are broken across lines
+    #[arg(long, value_name = "COLUMNS")]
+    pub max_width: Option<usize>,
+
+    /// Add a trailing comma to broken lists and parameters
+    /// Defaults to `format.trailing_comma` in `rico.toml`
+    #[arg(long, overrides_with = "no_trailing_comma")]
+    pub trailing_comma: bool,
+
+    /// Do not add trailing commas, the default
+    #[arg(long, overrides_with = "trailing_comma")]
+    pub no_trailing_comma: bool,
+
+    /// Write canonical output: sorted includes, namespaces and fields
+    /// Defaults to `format.canonical` in `rico.toml`
+    #[arg(long, overrides_with = "no_canonical")]
+    pub canonical: bool,
+
+    /// Keep the order of includes, namespaces and fields, the default
+    #[arg(long, overrides_with = "canonical")]
+    pub no_canonical: bool,
+}
+
+impl WriterArgs {
+    /// Fills options that were not given on the command line from the configuration
+    pub fn merge(&mut self, config: &FormatConfig) {
+        self.max_width = self.max_width.or(config.max_width);
+        self.trailing_comma = resolve_flag(
+            self.trailing_comma,
+            self.no_trailing_comma,
+            config.trailing_comma,
+            false,
+        );
+        self.no_trailing_comma = !self.trailing_comma;
+        self.canonical = resolve_flag(self.canonical, self.no_canonical, config.canonical, false);
+        self.no_canonical = !self.canonical;
+    }
+
+    pub fn options(&self) -> WriterOptions {
+        WriterOptions {
+            max_width: self.max_width,
+            trailing_comma: self.trailing_comma,
+            canonical: self.canonical,
+            ..Default::default()
+        }
+    }
+}
+
+/// Resolves a flag given as `--flag` or `--no-flag`, which override each other
+/// so the last one wins, falling back to the configuration and then `default`
+pub(crate) fn resolve_flag(
+    enabled: bool,
+    disabled: bool,
+    config: Option<bool>,
+    default: bool,
+) -> bool {
+    match (enabled, disabled) {
+        (true, _) => true,
+        (_, true) => false,
+        _ => config.unwrap_or(default),
+    }
+}
@@ -0,0 +1,387 @@
+//! `rico-scan parse`: validates Thrift files and optionally writes their JSON AST.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::Args;
+use miette::{miette, Report, Result};
+use rico::ast::Document;
+use rico::parser::Parser as ThriftParser;
+
+use super::{resolve_flag, InputArgs};
+use crate::cache::{Cache, Entry};
+use crate::config::Config;
+use crate::files::{read_input, read_stdin, with_source, write_json, Layout, OutputLayout};
+use crate::pipeline::{finish, process_files, write_stdout};
+use crate::rules::{self, Rule};
+use crate::watch::{watch, WatchState};
+
+/// Arguments of the parse command
+#[derive(Args, Debug)]
+pub struct ParseArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Optional output directory for JSON AST files
+    /// The output mirrors the directory tree under `--path`
+    /// If not provided, files will only be validated without generating output
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Layout of the output directory
+    /// Defaults to `output.layout` in `rico.toml`, or `mirror`
+    #[arg(long, value_enum)]
+    pub layout: Option<OutputLayout>,
+
+    /// Optional path of a single JSON file combining all ASTs,
+    /// keyed by the relative path of each Thrift file
+    #[arg(long, value_name = "FILE")]
+    pub combined: Option<PathBuf>,
+
+    /// Optional flag to enable pretty JSON output
+    /// Defaults to `output.pretty` in `rico.toml`
+    #[arg(long, overrides_with = "no_pretty")]
+    pub pretty: bool,
+
+    /// Write compact JSON output, the default
+    #[arg(long, overrides_with = "pretty")]
+    pub no_pretty: bool,
+
+    /// Keep running and re-parse files as they change
+    #[arg(long, conflicts_with = "format")]
+    pub watch: bool,
+
+    /// Clear the screen before each update in watch mode
+    #[arg(long, requires = "watch")]
+    pub clear: bool,
+
+    /// Time in milliseconds to wait for further changes before re-parsing
+    #[arg(long, value_name = "MS", default_value_t = 200, requires = "watch")]
+    pub debounce: u64,
+
+    /// Directory of the cache used to skip unchanged files
+    /// Defaults to `.rico-cache` next to `rico.toml`; without a configuration
+    /// file, files are only cached when this is given
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Parse all files, ignoring and not updating the cache
+    /// The cache is also bypassed when `unresolved-include` is enabled,
+    /// since its result depends on other files
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Validation rules to check on every parsed file
+    /// Replaces `validation.rules` in `rico.toml`
+    #[arg(long = "rule", value_enum, value_name = "RULE")]
+    pub rules: Vec<Rule>,
+
+    /// Warn about keywords used as names, such as `1: binary binary`
+    /// Defaults to `validation.strict` in `rico.toml`
+    #[arg(long, overrides_with = "no_strict")]
+    pub strict: bool,
+
+    /// Accept keywords used as names without a warning, the default
+    #[arg(long, overrides_with = "strict")]
+    pub no_strict: bool,
+
+    /// Directories searched for included files by the `unresolved-include` rule
+    /// Replaces `input.search_paths` in `rico.toml`
+    #[arg(long = "search-path", value_name = "DIR")]
+    pub search_paths: Vec<PathBuf>,
+}
+
+impl ParseArgs {
+    /// Fills options that were not given on the command line from the configuration
+    pub fn merge(&mut self, config: &Config) -> Result<()> {
+        self.input.merge(&config.input)?;
+        self.output = self.output.take().or_else(|| config.output.dir.clone());
+        self.layout = self.layout.or(config.output.layout);
+        self.combined = self
+            .combined
+            .take()
+            .or_else(|| config.output.combined.clone());
+        self.pretty = resolve_flag(self.pretty, self.no_pretty, config.output.pretty, false);
+        self.no_pretty = !self.pretty;
+        self.cache_dir = self
+            .cache_dir
+            .take()
+            .or_else(|| config.output.cache_dir.clone());
+        if self.rules.is_empty() {
+            self.rules = config.validation.rules.clone();
+        }
+        self.strict = resolve_flag(self.strict, self.no_strict, config.validation.strict, false);
+        self.no_strict = !self.strict;
+        if self.search_paths.is_empty() {
+            self.search_paths = config.input.search_paths.clone();
+        }
+        Ok(())
+    }
+
+    /// Returns the output layout for the input paths
+    pub fn layout(&self) -> Layout {
+        Layout::new(&self.input.paths, self.layout.unwrap_or_default())
+    }
+
+    /// Returns the JSON output path of an input file, if an output directory is given
+    fn output_path(&self, layout: &Layout, input: &Path) -> Option<PathBuf> {
+        self.output
+            .as_deref()
+            .map(|dir| layout.output_path(dir, input, "json"))
+    }
+}
+
+/// Processes a single Thrift file
+///
+/// This function will:
+/// 1. Read the file content
+/// 2. Parse it using the Rico parser
+/// 3. Check the enabled validation rules
+/// 4. Optionally write the AST as JSON if an output directory is provided
+///
+/// # Arguments
+///
+/// * `input` - Path to the input Thrift file
+/// * `args` - Parse arguments with the output and validation settings
+/// * `layout` - Maps the input file to its output path
+///
+/// # Returns
+///
+/// * `Ok(parsed)` with the AST and the parser warnings if processing succeeded
+/// * `Err` with a detailed error message if any step failed
+pub fn process_file(input: &Path, args: &ParseArgs, layout: &Layout) -> Result<Parsed> {
+    let content = read_input(input, args.input.encoding)?.text;
+    let output_path = args.output_path(layout, input);
+    parse_content(input, &content, output_path.as_deref(), args)
+}
+
+/// A parsed Thrift file
+pub struct Parsed {
+    pub document: Document,
+    /// Parser warnings, such as invalid escape sequences, and keywords used as
+    /// names in strict mode
+    pub warnings: Vec<Report>,
+}
+
+/// Parses the content of a Thrift file, checks the enabled rules and writes
+/// its JSON AST to `output_path`
+fn parse_content(
+    input: &Path,
+    content: &str,
+    output_path: Option<&Path>,
+    args: &ParseArgs,
+) -> Result<Parsed> {
+    let mut parser = ThriftParser::new(content).with_strict(args.strict);
+    let ast = parser
+        .parse()
+        .map_err(|error| with_source(error, input, content))?;
+    let warnings = parser
+        .warnings()
+        .iter()
+        .map(|warning| with_source(warning.clone(), input, content))
+        .collect();
+
+    rules::check(&ast, input, &args.rules, &args.search_paths)
+        .map_err(|violation| with_source(violation, input, content))?;
+
+    if let Some(output_path) = output_path {
+        write_json(&ast, output_path, args.pretty)?;
+    }
+
+    Ok(Parsed {
+        document: ast,
+        warnings,
+    })
+}
+
+/// The result of processing a file with the cache enabled
+struct Processed {
+    /// The AST, only loaded for cached files when a combined output is requested
+    document: Option<Document>,
+    /// Parser warnings, always empty for cached files
+    warnings: Vec<Report>,
+    /// Whether parsing and writing were skipped
+    cached: bool,
+}
+
+/// Processes a single Thrift file, skipping it if the cache shows that the
+/// file and its output settings are unchanged since the last run
+/// Files with warnings are not cached, so their warnings are reported on every run
+fn process_file_cached(
+    input: &Path,
+    args: &ParseArgs,
+    layout: &Layout,
+    cache: &Cache,
+) -> Result<Processed> {
+    let content = read_input(input, args.input.encoding)?.text;
+    let output_path = args.output_path(layout, input);
+    let entry = Entry::new(
+        &content,
+        output_path.clone(),
+        args.pretty,
+        &args.rules,
+        args.strict,
+    );
+    let needs_ast = args.combined.is_some();
+
+    if cache.is_fresh(input, &entry) {
+        if !needs_ast {
+            return Ok(Processed {
+                document: None,
+                warnings: Vec::new(),
+                cached: true,
+            });
+        }
+        if let Some(document) = cache.load_ast(&entry.hash) {
+            return Ok(Processed {
+                document: Some(document),
+                warnings: Vec::new(),
+                cached: true,
+            });
+        }
+    }
+
+    let parsed = parse_content(input, &content, output_path.as_deref(), args)?;
+    if parsed.warnings.is_empty() {
+        cache.store(input, entry, needs_ast.then_some(&parsed.document));
+    }
+    Ok(Processed {
+        document: Some(parsed.document),
+        warnings: parsed.warnings,
+        cached: false,
+    })
+}
+
+/// Parses a single document from stdin and prints its JSON AST to stdout
+/// Errors are rendered to stderr, attributed to `name`
+fn run_stdin(args: &ParseArgs, name: &Path) -> Result<ExitCode> {
+    if args.watch {
+        return Err(miette!("--watch cannot be used when reading from stdin"));
+    }
+
+    let content = read_stdin(args.input.encoding, name)?.text;
+    let ast = match parse_content(name, &content, None, args) {
+        Ok(parsed) => {
+            for warning in parsed.warnings {
+                eprintln!("{:?}", warning);
+            }
+            parsed.document
+        }
+        Err(error) => {
+            eprintln!("{:?}", error);
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let json = if args.pretty {
+        serde_json::to_string_pretty(&ast)
+    } else {
+        serde_json::to_string(&ast)
+    }
+    .map_err(|e| miette!("Failed to serialize the AST: {}", e))?;
+    write_stdout(format!("{}\n", json))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn run(args: ParseArgs) -> Result<ExitCode> {
+    if let Some(name) = args.input.stdin() {
+        return run_stdin(&args, name);
+    }
+
+    let start_time = Instant::now();
+    let human = args.input.human();
+    let Some(thrift_files) = args.input.discover("thrift", "Thrift")? else {
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let layout = args.layout();
+
+    if let Some(output_dir) = &args.output {
+        layout.check_output_collisions(&thrift_files, output_dir, "json")?;
+        fs::create_dir_all(output_dir)
+            .map_err(|e| miette!("Failed to create output directory: {}", e))?;
+        if human {
+            write_stdout(format!("📁 Output directory: {}\n", output_dir.display()))?;
+        }
+    }
+
+    let use_cache = !args.no_cache && !args.rules.contains(&Rule::UnresolvedInclude);
+    let cache = args
+        .cache_dir
+        .as_deref()
+        .filter(|_| use_cache)
+        .map(Cache::open);
+    let outcome = process_files(&thrift_files, human, |file| match &cache {
+        Some(cache) => process_file_cached(file, &args, &layout, cache),
+        None => process_file(file, &args, &layout).map(|parsed| Processed {
+            document: Some(parsed.document),
+            warnings: parsed.warnings,
+            cached: false,
+        }),
+    });
+    let cached = cache.map(|cache| {
+        cache.save();
+        outcome
+            .succeeded
+            .iter()
+            .filter(|(_, processed)| processed.cached)
+            .count()
+    });
+
+    if let Some(combined) = &args.combined {
+        let documents = outcome
+            .succeeded
+            .iter()
+            .filter_map(|(file, processed)| Some((file.as_path(), processed.document.as_ref()?)));
+        write_combined(documents, &layout, combined, args.pretty)?;
+        if human {
+            write_stdout(format!("📦 Combined output: {}\n", combined.display()))?;
+        }
+    }
+
+    if args.watch {
+        let mut state = WatchState {
+            documents: BTreeMap::new(),
+            warnings: BTreeMap::new(),
+            failures: outcome
+                .failed
+                .into_iter()
+                .map(|(file, error)| (file.clone(), error))
+                .collect(),
+        };
+        for (file, processed) in outcome.succeeded {
+            state.insert(file, processed.document, processed.warnings);
+        }
+        return watch(&args, &layout, state);
+    }
+
+    let warned = outcome
+        .succeeded
+        .into_iter()
+        .flat_map(|(file, processed)| processed.warnings.into_iter().map(move |w| (file, w)))
+        .collect();
+    Ok(finish(
+        &thrift_files,
+        outcome.failed,
+        warned,
+        cached,
+        args.input.format,
+        start_time,
+    ))
+}
+
+/// Writes a single JSON document combining all ASTs, keyed by relative path
+pub fn write_combined<'a>(
+    documents: impl Iterator<Item = (&'a Path, &'a Document)>,
+    layout: &Layout,
+    combined: &Path,
+    pretty: bool,
+) -> Result<()> {
+    let documents: BTreeMap<String, &Document> = documents
+        .map(|(file, ast)| (layout.key(file), ast))
+        .collect();
+    write_json(&documents, combined, pretty)
+}
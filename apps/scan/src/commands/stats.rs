@@ -0,0 +1,373 @@
+//! `rico-scan stats`: counts the definitions in a set of Thrift files.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::{Args, ValueEnum};
+use miette::{miette, Result};
+use rico::ast::{Annotations, Document, DocumentMembers, Field};
+use rico::parser::Parser as ThriftParser;
+use serde::Serialize;
+
+use super::{InputArgs, OutputFormat};
+use crate::encoding::Encoding;
+use crate::files::{read_input, with_source};
+use crate::pipeline::{finish, process_files, write_stdout};
+use crate::report::ReportFormat;
+
+/// Arguments of the stats command
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// `--format` selects the format of the statistics
+    /// With `json` and `csv`, failures are written to stderr instead of a report
+    #[command(flatten)]
+    pub input: InputArgs<StatsFormat>,
+}
+
+/// Output format of the statistics
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsFormat {
+    /// Aligned tables, followed by the scan summary
+    #[default]
+    Table,
+    /// A JSON object with per-file and total counts
+    Json,
+    /// One row per file plus a total row
+    Csv,
+}
+
+impl OutputFormat for StatsFormat {
+    const DEFAULT: &'static str = "table";
+
+    fn is_human(&self) -> bool {
+        *self == StatsFormat::Table
+    }
+}
+
+/// Definition counts of one or more documents
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct Counts {
+    pub structs: usize,
+    pub unions: usize,
+    pub exceptions: usize,
+    pub enums: usize,
+    pub services: usize,
+    pub functions: usize,
+    pub fields: usize,
+    pub typedefs: usize,
+    pub consts: usize,
+    /// Number of annotations by key
+    pub annotations: BTreeMap<String, usize>,
+    /// Number of namespace declarations by language
+    pub namespaces: BTreeMap<String, usize>,
+}
+
+impl Counts {
+    /// Counts the definitions of a document
+    pub fn from_document(document: &Document) -> Self {
+        let mut counts = Self::default();
+        for member in &document.members {
+            match member {
+                DocumentMembers::Struct(s) => {
+                    counts.structs += 1;
+                    counts.add_annotations(&s.annotations);
+                    counts.add_fields(&s.members);
+                }
+                DocumentMembers::Union(u) => {
+                    counts.unions += 1;
+                    counts.add_annotations(&u.annotations);
+                    counts.add_fields(&u.members);
+                }
+                DocumentMembers::Exception(e) => {
+                    counts.exceptions += 1;
+                    counts.add_annotations(&e.annotations);
+                    counts.add_fields(&e.members);
+                }
+                DocumentMembers::Enum(e) => {
+                    counts.enums += 1;
+                    counts.add_annotations(&e.annotations);
+                    for member in &e.members {
+                        counts.add_annotations(&member.annotations);
+                    }
+                }
+                DocumentMembers::Service(s) => {
+                    counts.services += 1;
+                    counts.functions += s.members.len();
+                    counts.add_annotations(&s.annotations);
+                    for function in &s.members {
+                        counts.add_annotations(&function.annotations);
+                        for param in function
+                            .params
+                            .iter()
+                            .chain(function.throws.iter().flatten())
+                        {
+                            counts.add_annotations(&param.annotations);
+                        }
+                    }
+                }
+                DocumentMembers::Typedef(_) => counts.typedefs += 1,
+                DocumentMembers::Const(_) => counts.consts += 1,
+                DocumentMembers::Namespace(ns) => {
+                    *counts.namespaces.entry(ns.scope.value.clone()).or_default() += 1;
+                }
+                DocumentMembers::Include(_) => {}
+            }
+        }
+        counts
+    }
+
+    /// Counts the fields of a struct, union or exception and their annotations
+    fn add_fields(&mut self, fields: &[Field]) {
+        self.fields += fields.len();
+        for field in fields {
+            self.add_annotations(&field.annotations);
+        }
+    }
+
+    fn add_annotations(&mut self, annotations: &Option<Annotations>) {
+        for annotation in annotations.iter().flat_map(|a| &a.members) {
+            *self
+                .annotations
+                .entry(annotation.name.value.clone())
+                .or_default() += 1;
+        }
+    }
+
+    /// Returns the definition counts as labeled rows
+    fn rows(&self) -> [(&'static str, usize); 9] {
+        [
+            ("structs", self.structs),
+            ("unions", self.unions),
+            ("exceptions", self.exceptions),
+            ("enums", self.enums),
+            ("services", self.services),
+            ("functions", self.functions),
+            ("fields", self.fields),
+            ("typedefs", self.typedefs),
+            ("consts", self.consts),
+        ]
+    }
+}
+
+impl AddAssign<&Counts> for Counts {
+    fn add_assign(&mut self, other: &Counts) {
+        self.structs += other.structs;
+        self.unions += other.unions;
+        self.exceptions += other.exceptions;
+        self.enums += other.enums;
+        self.services += other.services;
+        self.functions += other.functions;
+        self.fields += other.fields;
+        self.typedefs += other.typedefs;
+        self.consts += other.consts;
+        for (key, count) in &other.annotations {
+            *self.annotations.entry(key.clone()).or_default() += count;
+        }
+        for (language, count) in &other.namespaces {
+            *self.namespaces.entry(language.clone()).or_default() += count;
+        }
+    }
+}
+
+/// Per-file and total counts
+#[derive(Serialize, Debug)]
+struct Inventory<'a> {
+    files: Vec<FileCounts<'a>>,
+    total: Counts,
+}
+
+#[derive(Serialize, Debug)]
+struct FileCounts<'a> {
+    file: &'a Path,
+    #[serde(flatten)]
+    counts: Counts,
+}
+
+impl<'a> Inventory<'a> {
+    fn new(succeeded: Vec<(&'a PathBuf, Counts)>) -> Self {
+        let mut total = Counts::default();
+        let files = succeeded
+            .into_iter()
+            .map(|(file, counts)| {
+                total += &counts;
+                FileCounts { file, counts }
+            })
+            .collect();
+        Self { files, total }
+    }
+
+    /// Renders the per-file table followed by the totals, annotations and namespaces
+    fn to_table(&self) -> String {
+        let width = self
+            .files
+            .iter()
+            .map(|entry| entry.file.display().to_string().len())
+            .chain(std::iter::once("total".len()))
+            .max()
+            .unwrap_or_default();
+
+        let mut out = format!("{:<width$}", "file");
+        for (label, _) in self.total.rows() {
+            out.push_str(&format!(" {:>10}", label));
+        }
+        out.push('\n');
+        let rows = self
+            .files
+            .iter()
+            .map(|entry| (entry.file.display().to_string(), &entry.counts))
+            .chain(std::iter::once(("total".to_string(), &self.total)));
+        for (name, counts) in rows {
+            out.push_str(&format!("{:<width$}", name));
+            for (_, count) in counts.rows() {
+                out.push_str(&format!(" {:>10}", count));
+            }
+            out.push('\n');
+        }
+
+        let sections = [
+            ("annotation", &self.total.annotations),
+            ("namespace", &self.total.namespaces),
+        ];
+        for (title, counts) in sections {
+            if counts.is_empty() {
+                continue;
+            }
+            let width = counts
+                .keys()
+                .map(String::len)
+                .chain(std::iter::once(title.len()))
+                .max()
+                .unwrap_or_default();
+            out.push_str(&format!("\n{:<width$} {:>10}\n", title, "count"));
+            for (key, count) in counts {
+                out.push_str(&format!("{:<width$} {:>10}\n", key, count));
+            }
+        }
+        out
+    }
+
+    /// Renders one row per file and a total row
+    /// Every annotation key and namespace language gets its own column
+    fn to_csv(&self) -> String {
+        let annotation_keys: BTreeSet<&String> = self.total.annotations.keys().collect();
+        let languages: BTreeSet<&String> = self.total.namespaces.keys().collect();
+
+        let mut header = vec!["file".to_string()];
+        header.extend(self.total.rows().iter().map(|(label, _)| label.to_string()));
+        header.extend(
+            annotation_keys
+                .iter()
+                .map(|key| format!("annotation:{}", key)),
+        );
+        header.extend(
+            languages
+                .iter()
+                .map(|language| format!("namespace:{}", language)),
+        );
+
+        let mut out = csv_row(&header);
+        let rows = self
+            .files
+            .iter()
+            .map(|entry| (entry.file.display().to_string(), &entry.counts))
+            .chain(std::iter::once(("total".to_string(), &self.total)));
+        for (name, counts) in rows {
+            let mut row = vec![name];
+            row.extend(counts.rows().iter().map(|(_, count)| count.to_string()));
+            row.extend(annotation_keys.iter().map(|key| {
+                counts
+                    .annotations
+                    .get(*key)
+                    .copied()
+                    .unwrap_or(0)
+                    .to_string()
+            }));
+            row.extend(languages.iter().map(|language| {
+                counts
+                    .namespaces
+                    .get(*language)
+                    .copied()
+                    .unwrap_or(0)
+                    .to_string()
+            }));
+            out.push_str(&csv_row(&row));
+        }
+        out
+    }
+}
+
+/// Joins the cells of a CSV row, quoting cells that need it
+pub fn csv_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.clone()
+            }
+        })
+        .collect();
+    format!("{}\n", cells.join(","))
+}
+
+/// Parses a single Thrift file and counts its definitions
+fn process_file(input: &Path, encoding: Option<&'static Encoding>) -> Result<Counts> {
+    let content = read_input(input, encoding)?.text;
+    let document = ThriftParser::new(&content)
+        .parse()
+        .map_err(|error| with_source(error, input, &content))?;
+    Ok(Counts::from_document(&document))
+}
+
+pub fn run(args: StatsArgs) -> Result<ExitCode> {
+    let start_time = Instant::now();
+    let table = args.input.human();
+    // Statistics in JSON or CSV are the only output on stdout
+    let thrift_files = if table {
+        let Some(thrift_files) = args.input.discover("thrift", "Thrift")? else {
+            return Ok(ExitCode::SUCCESS);
+        };
+        thrift_files
+    } else {
+        args.input
+            .collect("thrift")
+            .map_err(|e| e.wrap_err("Failed to collect Thrift files"))?
+    };
+
+    let outcome = process_files(&thrift_files, table, |file| {
+        process_file(file, args.input.encoding)
+    });
+    let inventory = Inventory::new(outcome.succeeded);
+
+    if table {
+        write_stdout(format!("\n{}", inventory.to_table()))?;
+        return Ok(finish(
+            &thrift_files,
+            outcome.failed,
+            Vec::new(),
+            None,
+            ReportFormat::Human,
+            start_time,
+        ));
+    }
+
+    let output = match args.input.format {
+        StatsFormat::Json => serde_json::to_string_pretty(&inventory)
+            .map_err(|e| miette!("Failed to serialize statistics: {}", e))?,
+        _ => inventory.to_csv(),
+    };
+    write_stdout(format!("{}\n", output.trim_end()))?;
+
+    for (_, error) in &outcome.failed {
+        eprintln!("{:?}", error);
+    }
+    Ok(if outcome.failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,112 @@
+//! `rico-scan to-thrift`: converts JSON AST files back to Thrift IDL.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::Args;
+use miette::{miette, LabeledSpan, NamedSource, Result, SourceOffset};
+use rico::ast::Document;
+use rico::writer::{Writer, WriterOptions};
+
+use super::{InputArgs, WriterArgs};
+use crate::config::{Config, InputConfig};
+use crate::encoding::Encoding;
+use crate::files::{read_input, write_file, Layout, OutputLayout};
+use crate::pipeline::{finish, process_files, write_stdout};
+
+/// Arguments of the to-thrift command
+#[derive(Args, Debug)]
+pub struct ToThriftArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Output directory for the Thrift files
+    /// The output mirrors the directory tree under `--path`
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    #[command(flatten)]
+    pub writer: WriterArgs,
+}
+
+impl ToThriftArgs {
+    /// Fills options that were not given on the command line from the configuration
+    /// The input defaults to `output.dir`, where `parse` writes the JSON AST
+    pub fn merge(&mut self, config: &Config) -> Result<()> {
+        let input = InputConfig {
+            paths: config.output.dir.iter().cloned().collect(),
+            ..InputConfig::default()
+        };
+        self.input.merge(&input)?;
+        self.writer.merge(&config.format);
+        Ok(())
+    }
+}
+
+/// Converts a single JSON AST file to Thrift IDL
+///
+/// The document is validated before writing, so hand-edited or generated
+/// ASTs that cannot be represented as Thrift are reported instead of
+/// producing invalid IDL.
+fn process_file(
+    input: &Path,
+    layout: &Layout,
+    output_dir: &Path,
+    options: &WriterOptions,
+    encoding: Option<&'static Encoding>,
+) -> Result<()> {
+    let content = read_input(input, encoding)?.text;
+    let document: Document = serde_json::from_str(&content).map_err(|e| {
+        let offset = SourceOffset::from_location(&content, e.line(), e.column());
+        miette!(
+            code = "rico_scan::invalid_ast",
+            labels = vec![LabeledSpan::at_offset(offset.offset(), "here")],
+            "Invalid JSON AST: {}",
+            e
+        )
+        .with_source_code(NamedSource::new(
+            input.display().to_string(),
+            content.clone(),
+        ))
+    })?;
+
+    let thrift = Writer::with_options(options.clone())
+        .try_write(&document)
+        .map_err(|e| {
+            miette::Report::new(e).wrap_err(format!("Failed to write {}", input.display()))
+        })?;
+
+    let output_path = layout.output_path(output_dir, input, "thrift");
+    write_file(&output_path, &thrift)
+}
+
+pub fn run(args: ToThriftArgs) -> Result<ExitCode> {
+    let start_time = Instant::now();
+    let Some(json_files) = args.input.discover("json", "JSON")? else {
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let layout = Layout::new(&args.input.paths, OutputLayout::Mirror);
+    layout.check_output_collisions(&json_files, &args.output, "thrift")?;
+    fs::create_dir_all(&args.output)
+        .map_err(|e| miette!("Failed to create output directory: {}", e))?;
+    if args.input.human() {
+        write_stdout(format!("📁 Output directory: {}\n", args.output.display()))?;
+    }
+
+    let options = args.writer.options();
+    let outcome = process_files(&json_files, args.input.human(), |file| {
+        process_file(file, &layout, &args.output, &options, args.input.encoding)
+    });
+
+    Ok(finish(
+        &json_files,
+        outcome.failed,
+        Vec::new(),
+        None,
+        args.input.format,
+        start_time,
+    ))
+}
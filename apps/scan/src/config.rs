@@ -0,0 +1,169 @@
+//! Project configuration loaded from `rico.toml`.
+//!
+//! The file is discovered by walking up from the working directory, so every
+//! invocation inside a project shares the same settings. Relative paths in the
+//! file are resolved against the directory containing it. Command line flags
+//! are merged on top and take precedence.
+//!
+//! ```toml
+//! [input]
+//! paths = ["idl"]
+//! exclude = ["third_party"]
+//! search_paths = ["idl/common"]
+//!
+//! [output]
+//! dir = "build/ast"
+//! layout = "mirror"
+//! pretty = true
+//!
+//! [format]
+//! max_width = 100
+//! trailing_comma = true
+//!
+//! [validation]
+//! rules = ["duplicate-field-id", "unresolved-include"]
+//! strict = true
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{miette, LabeledSpan, NamedSource, Result};
+use serde::Deserialize;
+
+use crate::files::OutputLayout;
+use crate::rules::Rule;
+
+/// The name of the configuration file
+pub const CONFIG_FILE: &str = "rico.toml";
+
+/// The name of the cache directory created next to the configuration file
+pub const DEFAULT_CACHE_DIR: &str = ".rico-cache";
+
+/// Contents of `rico.toml`
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub input: InputConfig,
+    pub output: OutputConfig,
+    pub format: FormatConfig,
+    pub validation: ValidationConfig,
+}
+
+/// Which files are processed
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct InputConfig {
+    /// Input roots, directories or files
+    pub paths: Vec<PathBuf>,
+    /// Glob patterns a file must match
+    pub include: Vec<String>,
+    /// Glob patterns of files and directories to skip
+    pub exclude: Vec<String>,
+    /// File extensions to process
+    pub extensions: Vec<String>,
+    /// Directories searched for included files
+    pub search_paths: Vec<PathBuf>,
+    /// Whether `.gitignore` and `.ignore` files are respected
+    pub respect_ignore_files: Option<bool>,
+    /// Encoding of files without a byte order mark, e.g. `gbk`
+    pub encoding: Option<String>,
+}
+
+/// Where and how JSON AST output is written
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Output directory for JSON AST files
+    pub dir: Option<PathBuf>,
+    /// Layout of the output directory
+    pub layout: Option<OutputLayout>,
+    /// Path of a single JSON file combining all ASTs
+    pub combined: Option<PathBuf>,
+    /// Whether JSON output is pretty printed
+    pub pretty: Option<bool>,
+    /// Directory of the parse cache
+    /// Defaults to `.rico-cache` next to `rico.toml`
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// Writer options used by `fmt` and `to-thrift`
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FormatConfig {
+    pub max_width: Option<usize>,
+    pub trailing_comma: Option<bool>,
+    pub canonical: Option<bool>,
+}
+
+/// Validation rules checked by `parse`
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationConfig {
+    pub rules: Vec<Rule>,
+    pub strict: Option<bool>,
+}
+
+impl Config {
+    /// Loads the configuration from `path`, or discovers `rico.toml` upward
+    /// from the working directory if no path is given
+    /// Returns the default configuration if no file is found
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::discover() {
+                Some(path) => path,
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| miette!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Config = toml::from_str(&content).map_err(|e| {
+            let labels = e
+                .span()
+                .map(|span| vec![LabeledSpan::at(span, "here")])
+                .unwrap_or_default();
+            miette!(
+                code = "rico_scan::invalid_config",
+                labels = labels,
+                "Invalid configuration: {}",
+                e.message()
+            )
+            .with_source_code(NamedSource::new(
+                path.display().to_string(),
+                content.clone(),
+            ))
+        })?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        config.resolve_paths(base);
+        config
+            .output
+            .cache_dir
+            .get_or_insert_with(|| base.join(DEFAULT_CACHE_DIR));
+        Ok(config)
+    }
+
+    /// Finds `rico.toml` in the working directory or its ancestors
+    fn discover() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        cwd.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Makes relative paths relative to the configuration directory
+    fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |path: &mut PathBuf| {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        };
+        self.input.paths.iter_mut().for_each(resolve);
+        self.input.search_paths.iter_mut().for_each(resolve);
+        self.output.dir.iter_mut().for_each(resolve);
+        self.output.combined.iter_mut().for_each(resolve);
+        self.output.cache_dir.iter_mut().for_each(resolve);
+    }
+}
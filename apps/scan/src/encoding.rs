@@ -0,0 +1,112 @@
+//! Decoding of input files in encodings other than UTF-8.
+//!
+//! Files are decoded before parsing, so the parser and diagnostics always see
+//! UTF-8 text. Byte offsets refer to the decoded text, so they do not match the
+//! original file once it contains non-ASCII text. Lines are unaffected, and
+//! reports count columns in characters, which are the same in both.
+//!
+//! The encoding is chosen in this order:
+//! 1. A byte order mark, which is stripped
+//! 2. The `--encoding` flag
+//! 3. UTF-8, if the file is valid UTF-8
+//! 4. A guess from the content, e.g. GBK or windows-1252
+
+use std::path::Path;
+
+use chardetng::EncodingDetector;
+pub use encoding_rs::Encoding;
+use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8};
+use miette::{miette, Result};
+
+/// Text decoded from an input file
+#[derive(Debug)]
+pub struct Decoded {
+    /// The decoded text, without byte order mark
+    pub text: String,
+    /// The encoding of the file
+    pub encoding: &'static Encoding,
+    /// Whether the file started with a byte order mark
+    pub bom: bool,
+}
+
+impl Decoded {
+    /// Encodes text in the encoding of the original file, restoring its byte
+    /// order mark, so rewritten files keep their encoding
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        if self.bom {
+            bytes.extend_from_slice(bom(self.encoding));
+        }
+        // encoding_rs only encodes UTF-16 as UTF-8, since it targets the web
+        if self.encoding == UTF_16LE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        } else if self.encoding == UTF_16BE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        } else {
+            bytes.extend_from_slice(&self.encoding.encode(text).0);
+        }
+        bytes
+    }
+}
+
+fn bom(encoding: &'static Encoding) -> &'static [u8] {
+    if encoding == UTF_16LE {
+        b"\xFF\xFE"
+    } else if encoding == UTF_16BE {
+        b"\xFE\xFF"
+    } else if encoding == UTF_8 {
+        b"\xEF\xBB\xBF"
+    } else {
+        b""
+    }
+}
+
+/// Parses an encoding label such as `utf-8`, `gbk` or `latin1`
+pub fn parse_label(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("unknown encoding `{}`", label))
+}
+
+/// Decodes the content of a file
+///
+/// # Arguments
+///
+/// * `bytes` - The raw file content
+/// * `encoding` - The encoding to use when the file has no byte order mark
+/// * `name` - The file name shown in errors
+pub fn decode(bytes: &[u8], encoding: Option<&'static Encoding>, name: &Path) -> Result<Decoded> {
+    let (encoding, bom, content) = match Encoding::for_bom(bytes) {
+        Some((encoding, length)) => (encoding, true, &bytes[length..]),
+        None => {
+            let encoding = encoding.unwrap_or_else(|| detect(bytes));
+            (encoding, false, bytes)
+        }
+    };
+
+    let text = encoding
+        .decode_without_bom_handling_and_without_replacement(content)
+        .ok_or_else(|| {
+            miette!(
+                code = "rico_scan::invalid_encoding",
+                help = "pass the encoding of the file with --encoding",
+                "Failed to decode {} as {}",
+                name.display(),
+                encoding.name()
+            )
+        })?;
+
+    Ok(Decoded {
+        text: text.into_owned(),
+        encoding,
+        bom,
+    })
+}
+
+/// Returns UTF-8 for valid UTF-8 content, or guesses the encoding otherwise
+fn detect(bytes: &[u8]) -> &'static Encoding {
+    if std::str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, false)
+}
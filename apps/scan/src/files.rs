@@ -0,0 +1,268 @@
+//! Input discovery and output path handling shared by all commands.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use clap::ValueEnum;
+use colored::Colorize;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{WalkBuilder, WalkState};
+use miette::{miette, NamedSource, Result};
+use serde::Deserialize;
+
+use crate::encoding::{decode, Decoded, Encoding};
+
+/// Options controlling which files are collected
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// File extensions to collect, without the leading dot
+    pub extensions: Vec<String>,
+    /// Glob patterns a file must match, relative to the input path
+    pub include: Vec<String>,
+    /// Glob patterns of files and directories to skip, relative to the input path
+    pub exclude: Vec<String>,
+    /// Whether `.gitignore` and `.ignore` files are respected
+    pub respect_ignore_files: bool,
+}
+
+/// Collects files based on the input path
+/// If the path is a file, returns a vector with just that file
+/// If the path is a directory, walks it in parallel and collects all files with
+/// a matching extension that are not excluded by globs or ignore files
+/// Symlinks are followed, symlink loops are reported and skipped
+pub fn collect_files(path: &Path, options: &WalkOptions) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        if has_extension(path, &options.extensions) {
+            return Ok(vec![path.to_path_buf()]);
+        }
+        return Err(miette!(
+            "File '{}' does not have a supported extension ({})",
+            path.display(),
+            options.extensions.join(", ")
+        ));
+    }
+
+    let overrides = build_overrides(path, options)?;
+    let walker = WalkBuilder::new(path)
+        .follow_links(true)
+        .git_ignore(options.respect_ignore_files)
+        .git_global(options.respect_ignore_files)
+        .git_exclude(options.respect_ignore_files)
+        .ignore(options.respect_ignore_files)
+        .parents(options.respect_ignore_files)
+        .require_git(false)
+        .overrides(overrides)
+        .build_parallel();
+
+    let files = Mutex::new(Vec::new());
+    let error = Mutex::new(None);
+    walker.run(|| {
+        Box::new(|entry| {
+            match entry {
+                Ok(entry) => {
+                    let is_file = entry.file_type().is_some_and(|t| t.is_file());
+                    if is_file && has_extension(entry.path(), &options.extensions) {
+                        files.lock().unwrap().push(entry.into_path());
+                    }
+                }
+                Err(e) if is_loop(&e) => {
+                    eprintln!("{} {}", "!".yellow(), e.to_string().yellow());
+                }
+                Err(e) => {
+                    *error.lock().unwrap() = Some(e);
+                    return WalkState::Quit;
+                }
+            }
+            WalkState::Continue
+        })
+    });
+
+    if let Some(e) = error.into_inner().unwrap() {
+        return Err(miette!("Failed to walk {}: {}", path.display(), e));
+    }
+    let mut files = files.into_inner().unwrap();
+    files.sort();
+    Ok(files)
+}
+
+/// Builds the glob overrides, include patterns whitelist files and exclude
+/// patterns are negated so they also prune directories
+fn build_overrides(path: &Path, options: &WalkOptions) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(path);
+    for pattern in &options.include {
+        builder
+            .add(pattern)
+            .map_err(|e| miette!("Invalid include pattern '{}': {}", pattern, e))?;
+    }
+    for pattern in &options.exclude {
+        builder
+            .add(&format!("!{}", pattern))
+            .map_err(|e| miette!("Invalid exclude pattern '{}': {}", pattern, e))?;
+    }
+    builder
+        .build()
+        .map_err(|e| miette!("Invalid glob patterns: {}", e))
+}
+
+fn is_loop(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. } | ignore::Error::WithDepth { err, .. } => is_loop(err),
+        _ => false,
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e == ext))
+}
+
+/// How output files are laid out in the output directory
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputLayout {
+    /// Mirror the directory tree of the input paths
+    #[default]
+    Mirror,
+    /// Write all files directly into the output directory
+    Flat,
+}
+
+/// Maps input files to relative paths and output paths
+pub struct Layout {
+    /// Directories that relative paths are computed from, one per input path
+    roots: Vec<PathBuf>,
+    /// Output layout
+    layout: OutputLayout,
+}
+
+impl Layout {
+    /// Creates the layout for the given input paths
+    /// For a single file the root is its parent directory
+    pub fn new(paths: &[PathBuf], layout: OutputLayout) -> Self {
+        let roots = paths
+            .iter()
+            .map(|path| {
+                if path.is_file() {
+                    path.parent().unwrap_or_else(|| Path::new("")).to_path_buf()
+                } else {
+                    path.clone()
+                }
+            })
+            .collect();
+        Self { roots, layout }
+    }
+
+    /// Returns the path of an input file relative to its input root
+    pub fn relative<'a>(&self, input: &'a Path) -> &'a Path {
+        self.roots
+            .iter()
+            .find_map(|root| input.strip_prefix(root).ok())
+            .unwrap_or(input)
+    }
+
+    /// Returns the relative path as a key for combined output documents
+    /// Separators are normalized to `/` so keys are stable across platforms
+    pub fn key(&self, input: &Path) -> String {
+        self.relative(input)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Returns the output path for an input file with the given extension
+    pub fn output_path(&self, output_dir: &Path, input: &Path, extension: &str) -> PathBuf {
+        let relative = match self.layout {
+            OutputLayout::Mirror => self.relative(input),
+            OutputLayout::Flat => input.file_name().map_or(input, Path::new),
+        };
+        output_dir.join(relative).with_extension(extension)
+    }
+
+    /// Checks that no two input files map to the same output path
+    /// Paths are compared case-insensitively to also catch collisions on
+    /// case-insensitive file systems
+    pub fn check_output_collisions(
+        &self,
+        files: &[PathBuf],
+        output_dir: &Path,
+        extension: &str,
+    ) -> Result<()> {
+        let mut seen: HashMap<String, &Path> = HashMap::new();
+        for file in files {
+            let output = self.output_path(output_dir, file, extension);
+            let key = output.to_string_lossy().to_lowercase();
+            if let Some(previous) = seen.insert(key, file) {
+                return Err(miette!(
+                    "Output collision: {} and {} both write to {}",
+                    previous.display(),
+                    file.display(),
+                    output.display()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Path argument that reads a single document from stdin
+pub const STDIN: &str = "-";
+
+/// Reads and decodes stdin
+/// `name` is the file name shown in errors
+pub fn read_stdin(encoding: Option<&'static Encoding>, name: &Path) -> Result<Decoded> {
+    let mut bytes = Vec::new();
+    io::stdin()
+        .read_to_end(&mut bytes)
+        .map_err(|e| miette!("Failed to read stdin: {}", e))?;
+    decode(&bytes, encoding, name)
+}
+
+/// Reads and decodes an input file
+/// See [`decode`] for how the encoding is chosen
+pub fn read_input(input: &Path, encoding: Option<&'static Encoding>) -> Result<Decoded> {
+    let bytes =
+        fs::read(input).map_err(|e| miette!("Failed to read {}: {}", input.display(), e))?;
+    decode(&bytes, encoding, input)
+}
+
+/// Writes a file, creating parent directories as needed
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| miette!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| miette!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Serializes a value to a JSON file
+/// # Arguments
+///
+/// * `value` - The value to serialize
+/// * `output_path` - The path where to write the JSON file
+pub fn write_json<T: serde::Serialize>(value: &T, output_path: &Path, pretty: bool) -> Result<()> {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+    .map_err(|e| miette!("Failed to serialize {}: {}", output_path.display(), e))?;
+    write_file(output_path, &json)
+}
+
+/// Attaches the file content to a diagnostic so it renders with source context
+pub fn with_source(
+    error: impl miette::Diagnostic + Send + Sync + 'static,
+    input: &Path,
+    content: &str,
+) -> miette::Report {
+    miette::Report::new(error).with_source_code(NamedSource::new(
+        input.display().to_string(),
+        content.to_string(),
+    ))
+}
@@ -0,0 +1,14 @@
+//! The commands and building blocks of the rico-scan CLI.
+//!
+//! The `rico-scan` binary parses the command line and dispatches to
+//! [`commands`]; the other modules are shared between the commands.
+
+pub mod cache;
+pub mod commands;
+pub mod config;
+pub mod encoding;
+pub mod files;
+pub mod pipeline;
+pub mod report;
+pub mod rules;
+pub mod watch;
@@ -5,31 +5,68 @@
 //! - Fast parallel processing of Thrift files
 //! - Detailed error reporting with source context
 //! - Optional JSON AST output
+//! - In-place formatting and format checks
+//! - Conversion of JSON AST back to Thrift IDL
 //! - Progress indication with ETA
+//! - Watch mode with incremental re-parsing
+//! - Content-hash cache to skip unchanged files
+//! - Project configuration in `rico.toml` and opt-in validation rules
+//! - Decoding of UTF-16 and legacy encodings such as GBK
+//! - Stdin/stdout pipelines for single documents
 //! - Colorful and informative terminal output
 //!
 //! # Usage
 //!
 //! ```bash
 //! # Just validate Thrift files
-//! rico-scan -p /path/to/thrift/files
+//! rico-scan parse -p /path/to/thrift/files
 //!
-//! # Parse and output JSON AST
-//! rico-scan -p /path/to/thrift/files -o /path/to/output
+//! # Parse and output JSON AST, mirroring the input directory tree
+//! rico-scan parse -p /path/to/thrift/files -o /path/to/output
+//!
+//! # Parse and output a single JSON document keyed by relative path
+//! rico-scan parse -p /path/to/thrift/files --combined /path/to/ast.json
+//!
+//! # Report failures as SARIF for code-scanning dashboards
+//! rico-scan parse -p /path/to/thrift/files --format sarif > rico.sarif
+//!
+//! # Format Thrift files in place, or only check that they are formatted
+//! rico-scan fmt -p /path/to/thrift/files
+//! rico-scan fmt -p /path/to/thrift/files --check
+//!
+//! # Convert JSON AST files back to Thrift IDL
+//! rico-scan to-thrift -p /path/to/json -o /path/to/thrift
+//!
+//! # Read from stdin and write the JSON AST or formatted IDL to stdout
+//! cat user.thrift | rico-scan parse -
+//! cat user.thrift | rico-scan fmt -
+//!
+//! # Re-parse files as they change
+//! rico-scan parse -p /path/to/thrift/files --watch
+//!
+//! # Count the definitions in a set of Thrift files
+//! rico-scan stats -p /path/to/thrift/files
 //! ```
+//!
+//! Settings are read from the nearest `rico.toml` in the working directory or
+//! its ancestors; command line flags take precedence.
+//!
+//! Options without a subcommand, e.g. `rico-scan -p dir`, run `rico-scan parse`.
+//!
+//! The process exits with a non-zero status if any file fails to process.
 
-use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
-use std::{fs, io};
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-use clap::Parser;
-use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
-use miette::{miette, NamedSource, Result};
-use rayon::{current_num_threads, prelude::*};
-use rico::parser::Parser as ThriftParser;
+use clap::{Parser, Subcommand};
+use miette::Result;
+
+use rico_scan::commands::{
+    self, fmt::FmtArgs, parse::ParseArgs, stats::StatsArgs, to_thrift::ToThriftArgs,
+};
+use rico_scan::config::Config;
 
 /// Command line arguments for rico-scan
 #[derive(Parser, Debug)]
@@ -38,202 +75,82 @@ use rico::parser::Parser as ThriftParser;
     version,
     about = "A high-performance CLI tool for parsing and validating Thrift IDL files"
 )]
-struct Args {
-    /// Directory path containing thrift files
-    #[arg(short, long)]
-    path: PathBuf,
-
-    /// Optional output directory for JSON AST files
-    /// If not provided, files will only be validated without generating output
-    #[arg(short, long)]
-    output: Option<PathBuf>,
-
-    /// Optional flag to enable pretty JSON output
-    #[arg(long)]
-    pretty: bool,
-}
-
-/// Statistics for tracking file processing progress
-struct Stats {
-    /// Number of files processed so far
-    processed: AtomicUsize,
-    /// Total number of files to process
-    total: usize,
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Path of the configuration file
+    /// Defaults to the nearest `rico.toml` in the working directory or its ancestors
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Ignore `rico.toml`
+    #[arg(long, global = true, conflicts_with = "config")]
+    no_config: bool,
 }
 
-/// Sets up a progress bar with a custom style for file processing
-fn setup_progress_bar(total: usize) -> ProgressBar {
-    let pb = ProgressBar::new(total as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
-            .unwrap()
-            .progress_chars("=>-"),
-    );
-    pb.enable_steady_tick(std::time::Duration::from_millis(100));
-    pb
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Parse and validate Thrift files, optionally writing their JSON AST
+    Parse(ParseArgs),
+    /// Format Thrift files in place, or check that they are formatted
+    Fmt(FmtArgs),
+    /// Convert JSON AST files back to Thrift IDL
+    ToThrift(ToThriftArgs),
+    /// Count the definitions in a set of Thrift files
+    Stats(StatsArgs),
 }
 
-/// Collects Thrift files based on the input path
-/// If the path is a Thrift file, returns a vector with just that file
-/// If the path is a directory, recursively collects all Thrift files
-fn collect_thrift_files(path: &Path) -> io::Result<Vec<PathBuf>> {
-    if path.is_file() {
-        if path.extension().and_then(|s| s.to_str()) == Some("thrift") {
-            return Ok(vec![path.to_path_buf()]);
+/// Returns the command line arguments, inserting the `parse` subcommand when
+/// the first argument after the global options is an option, so
+/// `rico-scan -p dir` keeps working
+fn args_with_default_command() -> Vec<OsString> {
+    let mut args: Vec<OsString> = env::args_os().collect();
+    let mut index = 1;
+    loop {
+        match args.get(index).and_then(|arg| arg.to_str()) {
+            Some("--config") => index += 2,
+            Some(arg) if arg == "--no-config" || arg.starts_with("--config=") => index += 1,
+            _ => break,
         }
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("File '{}' is not a Thrift file", path.display()),
-        ));
     }
-
-    let mut files = Vec::new();
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            files.extend(collect_thrift_files(&path)?);
-        } else if path.extension().and_then(|s| s.to_str()) == Some("thrift") {
-            files.push(path);
-        }
+    let starts_with_option = args
+        .get(index)
+        .and_then(|arg| arg.to_str())
+        .is_some_and(|arg| {
+            arg.starts_with('-') && !matches!(arg, "-h" | "--help" | "-V" | "--version")
+        });
+    if starts_with_option {
+        args.insert(index, "parse".into());
     }
-    Ok(files)
+    args
 }
 
-/// Writes the AST to a JSON file
-/// # Arguments
-///
-/// * `ast` - The AST to serialize
-/// * `output_path` - The path where to write the JSON file
-fn write_output(ast: rico::ast::Document, output_path: &Path, pretty: bool) -> io::Result<()> {
-    let json = if pretty {
-        serde_json::to_string_pretty(&ast)?
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse_from(args_with_default_command());
+    let config = if cli.no_config {
+        Config::default()
     } else {
-        serde_json::to_string(&ast)?
+        Config::load(cli.config.as_deref())?
     };
-    fs::write(output_path, json)
-}
-
-/// Processes a single Thrift file
-///
-/// This function will:
-/// 1. Read the file content
-/// 2. Parse it using the Rico parser
-/// 3. Optionally write the AST as JSON if an output directory is provided
-///
-/// # Arguments
-///
-/// * `input` - Path to the input Thrift file
-/// * `output_dir` - Optional output directory for JSON files
-///
-/// # Returns
-///
-/// * `Ok(())` if processing succeeded
-/// * `Err` with a detailed error message if any step failed
-fn process_file(input: &Path, output_dir: Option<&Path>, pretty: bool) -> Result<()> {
-    let content = fs::read_to_string(input)
-        .map_err(|e| miette!("Failed to read {}: {}", input.display(), e))?;
-    let mut parser = ThriftParser::new(&content);
-
-    let ast = parser.parse().map_err(|error| {
-        miette::Error::new(error).with_source_code(NamedSource::new(
-            input.display().to_string(),
-            content.clone(),
-        ))
-    })?;
 
-    if let Some(output_dir) = output_dir {
-        {
-            let file_name = input
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .ok_or_else(|| miette!("Invalid file name"))?;
-            let output_path = output_dir.join(format!("{}.json", file_name));
-            write_output(ast, &output_path, pretty)
-                .map_err(|e| miette!("Failed to write {}: {}", output_path.display(), e))?;
+    match cli.command {
+        Command::Parse(mut args) => {
+            args.merge(&config)?;
+            commands::parse::run(args)
         }
-    }
-
-    Ok(())
-}
-
-fn main() -> Result<()> {
-    let start_time = Instant::now();
-    let args = Args::parse();
-    let thrift_files = collect_thrift_files(&args.path)
-        .map_err(|e| miette!("Failed to collect Thrift files: {}", e))?;
-
-    if thrift_files.is_empty() {
-        println!(
-            "{} {} {}",
-            "!".yellow(),
-            "No Thrift files found in".yellow(),
-            args.path.display().to_string().yellow().underline()
-        );
-        return Ok(());
-    }
-
-    println!("📝 Found {} Thrift files", thrift_files.len());
-
-    if let Some(output_dir) = &args.output {
-        fs::create_dir_all(output_dir)
-            .map_err(|e| miette!("Failed to create output directory: {}", e))?;
-        println!("📁 Output directory: {}", output_dir.display());
-    }
-
-    let stats = Arc::new(Stats {
-        processed: AtomicUsize::new(0),
-        total: thrift_files.len(),
-    });
-
-    let pb = setup_progress_bar(stats.total);
-
-    // Process files in parallel
-    let results: Vec<_> = thrift_files
-        .par_iter()
-        .map(|file| {
-            let result = process_file(file, args.output.as_deref(), args.pretty);
-            stats.processed.fetch_add(1, Ordering::SeqCst);
-            pb.inc(1);
-            (file, result)
-        })
-        .collect();
-
-    pb.finish_and_clear();
-
-    // Report results
-    let (success, failures): (Vec<_>, Vec<_>) =
-        results.into_iter().partition(|(_, result)| result.is_ok());
-
-    let elapsed = start_time.elapsed();
-    let failures_count = failures.len();
-
-    // Only show error details if there are failures
-    if !failures.is_empty() {
-        println!("\n");
-        for (_, error) in failures {
-            eprintln!("{:?}", error.err().unwrap());
+        Command::Fmt(mut args) => {
+            args.input.merge(&config.input)?;
+            args.writer.merge(&config.format);
+            commands::fmt::run(args)
+        }
+        Command::ToThrift(mut args) => {
+            args.merge(&config)?;
+            commands::to_thrift::run(args)
+        }
+        Command::Stats(mut args) => {
+            args.input.merge(&config.input)?;
+            commands::stats::run(args)
         }
     }
-
-    // Print summary in one line
-    println!(
-        "{} {} {} {} {} {} {} {} {} {} {} {} {}",
-        "Done!".bright_green(),
-        "•".bright_black(),
-        "✅".green(),
-        format!("succeeded: {}", success.len()).green(),
-        "•".bright_black(),
-        "❌".red(),
-        format!("failed: {}", failures_count).red(),
-        "•".bright_black(),
-        "⚡".cyan(),
-        format!("threads: {}", current_num_threads()).cyan(),
-        "•".bright_black(),
-        "⏱".yellow(),
-        format!("time: {:.3}s", elapsed.as_secs_f32()).yellow()
-    );
-
-    Ok(())
 }
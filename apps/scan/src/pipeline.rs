@@ -0,0 +1,217 @@
+//! Parallel file processing with progress indication.
+//!
+//! Every command runs its per-file work through [`process_files`] and hands the
+//! results to [`finish`], which prints the failures and summary in the selected
+//! report format and computes the exit code.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use miette::{miette, Report, Result};
+use rayon::{current_num_threads, prelude::*};
+
+use crate::report::{Failure, ReportFormat, ScanReport};
+
+/// Statistics for tracking file processing progress
+struct Stats {
+    /// Number of files processed so far
+    processed: AtomicUsize,
+    /// Total number of files to process
+    total: usize,
+}
+
+/// Sets up a progress bar with a custom style for file processing
+fn setup_progress_bar(total: usize) -> ProgressBar {
+    let pb = ProgressBar::new(total as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .unwrap()
+            .progress_chars("=>-"),
+    );
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    pb
+}
+
+/// The results of processing a set of files
+pub struct Outcome<'a, T> {
+    /// Files that were processed successfully, with their results
+    pub succeeded: Vec<(&'a PathBuf, T)>,
+    /// Files that failed, with their errors
+    pub failed: Vec<(&'a PathBuf, Report)>,
+}
+
+/// Processes files in parallel, optionally showing a progress bar
+///
+/// # Arguments
+///
+/// * `files` - The files to process
+/// * `show_progress` - Whether to show the progress bar
+/// * `process` - The per-file work
+pub fn process_files<'a, T, F>(
+    files: &'a [PathBuf],
+    show_progress: bool,
+    process: F,
+) -> Outcome<'a, T>
+where
+    T: Send,
+    F: Fn(&PathBuf) -> Result<T> + Sync,
+{
+    let stats = Stats {
+        processed: AtomicUsize::new(0),
+        total: files.len(),
+    };
+
+    let pb = if show_progress {
+        setup_progress_bar(stats.total)
+    } else {
+        ProgressBar::hidden()
+    };
+
+    let results: Vec<_> = files
+        .par_iter()
+        .map(|file| {
+            let result = process(file);
+            stats.processed.fetch_add(1, Ordering::SeqCst);
+            pb.inc(1);
+            (file, result)
+        })
+        .collect();
+
+    pb.finish_and_clear();
+
+    let mut outcome = Outcome {
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (file, result) in results {
+        match result {
+            Ok(value) => outcome.succeeded.push((file, value)),
+            Err(error) => outcome.failed.push((file, error)),
+        }
+    }
+    outcome
+}
+
+/// Reports the failures, warnings and a summary, and returns the exit code
+///
+/// Human mode prints every warning and failure with source context followed
+/// by a one line summary. Machine formats print the report to stdout instead.
+/// Warnings do not affect the exit code. `cached` is the number of files
+/// served from the cache, if it is enabled.
+pub fn finish(
+    files: &[PathBuf],
+    failed: Vec<(&PathBuf, Report)>,
+    warned: Vec<(&PathBuf, Report)>,
+    cached: Option<usize>,
+    format: ReportFormat,
+    start_time: Instant,
+) -> ExitCode {
+    let elapsed = start_time.elapsed();
+    let failures_count = failed.len();
+    let exit_code = if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    };
+
+    if format != ReportFormat::Human {
+        let failures: Vec<_> = failed
+            .iter()
+            .map(|(file, error)| Failure::from_report(file, error))
+            .collect();
+        let warnings: Vec<_> = warned
+            .iter()
+            .map(|(file, warning)| Failure::from_report(file, warning))
+            .collect();
+        let report = ScanReport {
+            files,
+            failures: &failures,
+            warnings: &warnings,
+            cached,
+            elapsed: elapsed.as_secs_f32(),
+        };
+        let output = format!("{}\n", report.render(format).trim_end());
+        return exit_code_after(write_stdout(output), exit_code);
+    }
+
+    // Only show details if there are failures or warnings
+    if !failed.is_empty() || !warned.is_empty() {
+        if let Err(error) = write_stdout("\n\n") {
+            return exit_code_after(Err(error), exit_code);
+        }
+        for (_, report) in warned.iter().chain(&failed) {
+            eprintln!("{:?}", report);
+        }
+    }
+
+    let warnings = match warned.len() {
+        0 => String::new(),
+        count => format!(
+            "{} {} {} ",
+            "⚠".yellow(),
+            format!("warnings: {}", count).yellow(),
+            "•".bright_black()
+        ),
+    };
+
+    let cached = cached
+        .map(|cached| format!("{} {} ", "💾".blue(), format!("cached: {}", cached).blue()))
+        .map(|cached| format!("{}{} ", cached, "•".bright_black()))
+        .unwrap_or_default();
+
+    // Print summary in one line
+    let summary = format!(
+        "{} {} {} {} {} {} {} {} {}{}{} {} {} {} {}\n",
+        "Done!".bright_green(),
+        "•".bright_black(),
+        "✅".green(),
+        format!("succeeded: {}", files.len() - failures_count).green(),
+        "•".bright_black(),
+        "❌".red(),
+        format!("failed: {}", failures_count).red(),
+        "•".bright_black(),
+        warnings,
+        cached,
+        "⚡".cyan(),
+        format!("threads: {}", current_num_threads()).cyan(),
+        "•".bright_black(),
+        "⏱".yellow(),
+        format!("time: {:.3}s", elapsed.as_secs_f32()).yellow()
+    );
+
+    exit_code_after(write_stdout(summary), exit_code)
+}
+
+/// Writes `output` to stdout
+///
+/// A closed pipe, e.g. from `rico-scan parse -p dir | head`, is not an error:
+/// the reader has stopped because it has all the output it wants.
+pub fn write_stdout(output: impl AsRef<[u8]>) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    match stdout
+        .write_all(output.as_ref())
+        .and_then(|()| stdout.flush())
+    {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            Err(miette!("Failed to write stdout: {}", e))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns `exit_code`, or a failure if writing the report failed
+fn exit_code_after(written: Result<()>, exit_code: ExitCode) -> ExitCode {
+    match written {
+        Ok(()) => exit_code,
+        Err(error) => {
+            eprintln!("{:?}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
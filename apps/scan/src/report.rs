@@ -0,0 +1,312 @@
+//! Machine-readable scan reports.
+//!
+//! Converts the outcome of a scan into JSON, SARIF 2.1.0 or JUnit XML so CI
+//! systems and code-scanning dashboards can consume rico-scan results.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use miette::Report;
+use rico::line_index::{LineIndex, PositionEncoding};
+use serde::Serialize;
+use serde_json::json;
+
+/// Output format of the scan report
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// Colored diagnostics and a summary line
+    #[default]
+    Human,
+    /// A JSON document with a summary and all failures
+    Json,
+    /// SARIF 2.1.0 for code-scanning dashboards
+    Sarif,
+    /// JUnit XML with one test case per file
+    Junit,
+}
+
+/// A diagnostic of a file: an error that failed it, or a warning
+#[derive(Serialize, Debug)]
+pub struct Failure {
+    /// Path of the Thrift file
+    pub file: PathBuf,
+    /// Diagnostic code, e.g. `rico::parser::unexpected_token`
+    pub code: Option<String>,
+    /// Error message
+    pub message: String,
+    /// Help text attached to the diagnostic
+    pub help: Option<String>,
+    /// 1-based line of the error span
+    pub line: Option<usize>,
+    /// 1-based column of the error span, in characters
+    pub column: Option<usize>,
+    /// Length of the error span in characters
+    pub length: Option<usize>,
+}
+
+impl Failure {
+    /// Extracts the code, help text and span position from a diagnostic report
+    ///
+    /// Columns and lengths are counted in characters rather than bytes of the
+    /// decoded text, so they match the original file whatever its encoding.
+    pub fn from_report(file: &Path, report: &Report) -> Self {
+        let label = report.labels().and_then(|mut labels| labels.next());
+        let position = label.as_ref().and_then(|label| {
+            let source = report.source_code()?;
+            let contents = source.read_span(label.inner(), 0, 0).ok()?;
+            // miette counts columns in bytes, so re-read the span together with
+            // the start of its line and count characters instead
+            let start = contents.column();
+            let line_start = label.offset().checked_sub(start)?;
+            let text = source
+                .read_span(&(line_start..label.offset() + label.len()).into(), 0, 0)
+                .ok()?;
+            let text = std::str::from_utf8(text.data()).ok()?;
+            let column = LineIndex::new(text)
+                .position(start, PositionEncoding::Utf32)
+                .column;
+            let length = PositionEncoding::Utf32.len(text.get(start..)?);
+            Some((contents.line() + 1, column, length))
+        });
+
+        Self {
+            file: file.to_path_buf(),
+            code: report.code().map(|code| code.to_string()),
+            message: report.to_string(),
+            help: report.help().map(|help| help.to_string()),
+            line: position.map(|(line, _, _)| line),
+            column: position.map(|(_, column, _)| column),
+            length: position
+                .map(|(_, _, length)| length)
+                .or(label.map(|label| label.len())),
+        }
+    }
+
+    /// Returns the `file:line:column: message` text followed by the help text
+    fn details(&self) -> String {
+        let location = match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", self.file.display(), line, column),
+            _ => self.file.display().to_string(),
+        };
+        let mut details = format!("{}: {}", location, self.message);
+        if let Some(help) = &self.help {
+            write!(details, "\nhelp: {}", help).unwrap();
+        }
+        details
+    }
+}
+
+/// The outcome of a scan
+pub struct ScanReport<'a> {
+    /// All scanned files
+    pub files: &'a [PathBuf],
+    /// Files that failed to process
+    pub failures: &'a [Failure],
+    /// Warnings of files, which do not fail them
+    pub warnings: &'a [Failure],
+    /// Number of files served from the cache, if it is enabled
+    pub cached: Option<usize>,
+    /// Total processing time in seconds
+    pub elapsed: f32,
+}
+
+impl ScanReport<'_> {
+    /// Renders the report in the given machine-readable format
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Human => String::new(),
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Sarif => self.to_sarif(),
+            ReportFormat::Junit => self.to_junit(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let report = json!({
+            "summary": {
+                "total": self.files.len(),
+                "succeeded": self.files.len() - self.failures.len(),
+                "failed": self.failures.len(),
+                "warnings": self.warnings.len(),
+                "cached": self.cached,
+                "time": self.elapsed,
+            },
+            "failures": self.failures,
+            "warnings": self.warnings,
+        });
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+
+    /// Returns the failures and warnings with their SARIF level
+    fn diagnostics(&self) -> impl Iterator<Item = (&Failure, &'static str)> {
+        let failures = self.failures.iter().map(|failure| (failure, "error"));
+        let warnings = self.warnings.iter().map(|warning| (warning, "warning"));
+        failures.chain(warnings)
+    }
+
+    fn to_sarif(&self) -> String {
+        let mut rule_ids: Vec<&str> = self
+            .diagnostics()
+            .filter_map(|(failure, _)| failure.code.as_deref())
+            .collect();
+        rule_ids.sort_unstable();
+        rule_ids.dedup();
+
+        let rules: Vec<_> = rule_ids
+            .iter()
+            .map(|id| {
+                let (failure, _) = self
+                    .diagnostics()
+                    .find(|(failure, _)| failure.code.as_deref() == Some(id))
+                    .unwrap();
+                json!({
+                    "id": id,
+                    "shortDescription": { "text": failure.message },
+                    "help": { "text": failure.help.clone().unwrap_or_default() },
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = self
+            .diagnostics()
+            .map(|(failure, level)| {
+                let mut region = serde_json::Map::new();
+                if let (Some(line), Some(column)) = (failure.line, failure.column) {
+                    region.insert("startLine".into(), line.into());
+                    region.insert("startColumn".into(), column.into());
+                }
+                let message = match &failure.help {
+                    Some(help) => format!("{}: {}", failure.message, help),
+                    None => failure.message.clone(),
+                };
+                json!({
+                    "ruleId": failure.code.as_deref().unwrap_or("rico::error"),
+                    "level": level,
+                    "message": { "text": message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": file_uri(&failure.file) },
+                            "region": region,
+                        }
+                    }],
+                })
+            })
+            .collect();
+
+        let report = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+                "columnKind": "unicodeCodePoints",
+            }],
+        });
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+
+    fn to_junit(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+        writeln!(
+            output,
+            r#"<testsuites tests="{}" failures="{}" time="{:.3}">"#,
+            self.files.len(),
+            self.failures.len(),
+            self.elapsed
+        )
+        .unwrap();
+        writeln!(
+            output,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#,
+            env!("CARGO_PKG_NAME"),
+            self.files.len(),
+            self.failures.len(),
+            self.elapsed
+        )
+        .unwrap();
+
+        for file in self.files {
+            let name = escape_xml(&file.display().to_string());
+            let failure = self.failures.iter().find(|failure| &failure.file == file);
+            let warnings: Vec<_> = self
+                .warnings
+                .iter()
+                .filter(|warning| &warning.file == file)
+                .collect();
+            if failure.is_none() && warnings.is_empty() {
+                writeln!(
+                    output,
+                    r#"    <testcase name="{}" classname="thrift"/>"#,
+                    name
+                )
+                .unwrap();
+                continue;
+            }
+
+            writeln!(
+                output,
+                r#"    <testcase name="{}" classname="thrift">"#,
+                name
+            )
+            .unwrap();
+            if let Some(failure) = failure {
+                writeln!(
+                    output,
+                    r#"      <failure message="{}" type="{}">{}</failure>"#,
+                    escape_xml(&failure.message),
+                    escape_xml(failure.code.as_deref().unwrap_or("rico::error")),
+                    escape_xml(&failure.details())
+                )
+                .unwrap();
+            }
+            // Warnings do not fail the test case, so they are attached as output
+            if !warnings.is_empty() {
+                let details: Vec<_> = warnings
+                    .iter()
+                    .map(|warning| format!("warning: {}", warning.details()))
+                    .collect();
+                writeln!(
+                    output,
+                    "      <system-out>{}</system-out>",
+                    escape_xml(&details.join("\n"))
+                )
+                .unwrap();
+            }
+            writeln!(output, "    </testcase>").unwrap();
+        }
+
+        writeln!(output, "  </testsuite>").unwrap();
+        writeln!(output, "</testsuites>").unwrap();
+        output
+    }
+}
+
+/// Converts a file path to a SARIF artifact URI with forward slashes
+fn file_uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Escapes the XML special characters in attribute values and text
+pub fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,206 @@
+//! Validation rules checked on parsed documents.
+//!
+//! Rules go beyond the grammar and catch mistakes that parse fine but break
+//! code generation, such as duplicate field IDs. They are opt-in, enabled with
+//! `--rule` or the `validation.rules` list in `rico.toml`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use miette::{Diagnostic, SourceSpan};
+use rico::ast::{Common, Document, DocumentMembers, Field, LOC};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A validation rule
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Field IDs must be unique within a struct, union, exception or parameter list
+    DuplicateFieldId,
+    /// Every field must declare an explicit ID
+    MissingFieldId,
+    /// Top-level definition names must be unique within a file
+    DuplicateDefinition,
+    /// Included files must exist next to the file or in a search path
+    UnresolvedInclude,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum RuleViolation {
+    #[error("Duplicate field ID {id}")]
+    #[diagnostic(
+        code(rico_scan::rules::duplicate_field_id),
+        help("Field IDs identify fields on the wire and must be unique")
+    )]
+    DuplicateFieldId {
+        id: String,
+        #[label("This ID is already used")]
+        span: SourceSpan,
+        #[label("First used here")]
+        previous: SourceSpan,
+    },
+
+    #[error("Missing field ID for {name}")]
+    #[diagnostic(
+        code(rico_scan::rules::missing_field_id),
+        help("Declare an explicit field ID, e.g. `1: i32 {name}`")
+    )]
+    MissingFieldId {
+        name: String,
+        #[label("This field has no ID")]
+        span: SourceSpan,
+    },
+
+    #[error("Duplicate definition {name}")]
+    #[diagnostic(
+        code(rico_scan::rules::duplicate_definition),
+        help("Definition names must be unique within a file")
+    )]
+    DuplicateDefinition {
+        name: String,
+        #[label("This name is already defined")]
+        span: SourceSpan,
+        #[label("First defined here")]
+        previous: SourceSpan,
+    },
+
+    #[error("Unresolved include {path}")]
+    #[diagnostic(
+        code(rico_scan::rules::unresolved_include),
+        help("The file was not found next to this file or in any search path")
+    )]
+    UnresolvedInclude {
+        path: String,
+        #[label("This file does not exist")]
+        span: SourceSpan,
+    },
+}
+
+fn span(loc: &LOC) -> SourceSpan {
+    (loc.start.index..loc.end.index).into()
+}
+
+/// Checks the enabled rules on a document and returns the first violation
+///
+/// # Arguments
+///
+/// * `document` - The parsed document
+/// * `input` - Path of the file the document was parsed from
+/// * `rules` - The enabled rules
+/// * `search_paths` - Directories searched for included files
+pub fn check(
+    document: &Document,
+    input: &Path,
+    rules: &[Rule],
+    search_paths: &[PathBuf],
+) -> Result<(), RuleViolation> {
+    for rule in rules {
+        match rule {
+            Rule::DuplicateFieldId | Rule::MissingFieldId => {
+                for fields in field_lists(document) {
+                    check_fields(fields, *rule)?;
+                }
+            }
+            Rule::DuplicateDefinition => check_definitions(document)?,
+            Rule::UnresolvedInclude => check_includes(document, input, search_paths)?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns every list of fields in the document
+fn field_lists(document: &Document) -> Vec<&[Field]> {
+    let mut lists: Vec<&[Field]> = Vec::new();
+    for member in &document.members {
+        match member {
+            DocumentMembers::Struct(s) => lists.push(&s.members),
+            DocumentMembers::Union(u) => lists.push(&u.members),
+            DocumentMembers::Exception(e) => lists.push(&e.members),
+            DocumentMembers::Service(s) => {
+                for function in &s.members {
+                    lists.push(&function.params);
+                    if let Some(throws) = &function.throws {
+                        lists.push(throws);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    lists
+}
+
+fn check_fields(fields: &[Field], rule: Rule) -> Result<(), RuleViolation> {
+    let mut seen: HashMap<&str, &Common<String>> = HashMap::new();
+    for field in fields {
+        match &field.field_id {
+            Some(id) if rule == Rule::DuplicateFieldId => {
+                if let Some(previous) = seen.insert(&id.value, id) {
+                    return Err(RuleViolation::DuplicateFieldId {
+                        id: id.value.clone(),
+                        span: span(&id.loc),
+                        previous: span(&previous.loc),
+                    });
+                }
+            }
+            None if rule == Rule::MissingFieldId => {
+                return Err(RuleViolation::MissingFieldId {
+                    name: field.name.value.clone(),
+                    span: span(&field.name.loc),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_definitions(document: &Document) -> Result<(), RuleViolation> {
+    let mut seen: HashMap<&str, &Common<String>> = HashMap::new();
+    for member in &document.members {
+        let name = match member {
+            DocumentMembers::Const(c) => &c.name,
+            DocumentMembers::Typedef(td) => &td.name,
+            DocumentMembers::Enum(e) => &e.name,
+            DocumentMembers::Struct(s) => &s.name,
+            DocumentMembers::Union(u) => &u.name,
+            DocumentMembers::Exception(e) => &e.name,
+            DocumentMembers::Service(s) => &s.name,
+            DocumentMembers::Namespace(_) | DocumentMembers::Include(_) => continue,
+        };
+        if let Some(previous) = seen.insert(&name.value, name) {
+            return Err(RuleViolation::DuplicateDefinition {
+                name: name.value.clone(),
+                span: span(&name.loc),
+                previous: span(&previous.loc),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn check_includes(
+    document: &Document,
+    input: &Path,
+    search_paths: &[PathBuf],
+) -> Result<(), RuleViolation> {
+    let dir = input.parent().unwrap_or_else(|| Path::new(""));
+    for member in &document.members {
+        let DocumentMembers::Include(include) = member else {
+            continue;
+        };
+        let path = include.name.text();
+        let found = std::iter::once(dir)
+            .chain(search_paths.iter().map(PathBuf::as_path))
+            .any(|dir| dir.join(path.as_ref()).is_file());
+        if !found {
+            return Err(RuleViolation::UnresolvedInclude {
+                path: path.to_string(),
+                span: span(&include.name.loc),
+            });
+        }
+    }
+    Ok(())
+}
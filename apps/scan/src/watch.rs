@@ -0,0 +1,257 @@
+//! Watch mode for `rico-scan parse`.
+//!
+//! After the initial scan, the input directory is watched for changes. Each
+//! debounced batch of file system events re-parses only the affected files,
+//! updates their JSON output and redraws a compact diagnostics view.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use colored::{ColoredString, Colorize};
+use miette::{miette, Report, Result};
+use notify_debouncer_full::notify::{EventKind, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, DebouncedEvent};
+use rico::ast::Document;
+
+use crate::commands::parse::{process_file, write_combined, ParseArgs};
+use crate::files::Layout;
+use crate::pipeline::{process_files, write_stdout};
+use crate::report::Failure;
+
+/// The latest result of every file in scope
+pub struct WatchState {
+    /// ASTs of the files that parsed successfully
+    /// `None` for cached files whose AST was not loaded
+    pub documents: BTreeMap<PathBuf, Option<Document>>,
+    /// Parser warnings of the files that parsed successfully
+    pub warnings: BTreeMap<PathBuf, Vec<Report>>,
+    /// Errors of the files that failed
+    pub failures: BTreeMap<PathBuf, Report>,
+}
+
+impl WatchState {
+    /// Records a file that parsed successfully
+    pub fn insert(&mut self, file: &Path, document: Option<Document>, warnings: Vec<Report>) {
+        self.documents.insert(file.to_path_buf(), document);
+        if !warnings.is_empty() {
+            self.warnings.insert(file.to_path_buf(), warnings);
+        }
+    }
+
+    /// Forgets the last result of a file
+    fn remove(&mut self, file: &Path) {
+        self.documents.remove(file);
+        self.warnings.remove(file);
+        self.failures.remove(file);
+    }
+}
+
+/// Watches the input paths and re-parses changed files until interrupted
+pub fn watch(args: &ParseArgs, layout: &Layout, mut state: WatchState) -> Result<ExitCode> {
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(Duration::from_millis(args.debounce), None, tx)
+        .map_err(|e| miette!("Failed to start file watcher: {}", e))?;
+    for path in &args.input.paths {
+        debouncer
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|e| miette!("Failed to watch {}: {}", path.display(), e))?;
+    }
+
+    render(args, &state, None, None)?;
+
+    for events in rx {
+        let events = match events {
+            Ok(events) => events,
+            Err(errors) => {
+                for e in errors {
+                    eprintln!("{} {}", "!".yellow(), e.to_string().yellow());
+                }
+                continue;
+            }
+        };
+
+        let start_time = Instant::now();
+        let files = args.input.collect("thrift")?;
+        let changed = changed_files(&files, &events);
+
+        // Drop files that were deleted or fell out of scope, with their output
+        let removed: Vec<PathBuf> = state
+            .documents
+            .keys()
+            .chain(state.failures.keys())
+            .filter(|file| !files.contains(file))
+            .cloned()
+            .collect();
+        for file in &removed {
+            state.remove(file);
+            if let Some(output_dir) = &args.output {
+                let _ = fs::remove_file(layout.output_path(output_dir, file, "json"));
+            }
+        }
+
+        if changed.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        let collision = match &args.output {
+            Some(output_dir) => layout
+                .check_output_collisions(&files, output_dir, "json")
+                .err(),
+            None => None,
+        };
+        if collision.is_none() {
+            let outcome = process_files(&changed, false, |file| process_file(file, args, layout));
+            for (file, parsed) in outcome.succeeded {
+                state.remove(file);
+                state.insert(file, Some(parsed.document), parsed.warnings);
+            }
+            for (file, error) in outcome.failed {
+                state.remove(file);
+                state.failures.insert(file.clone(), error);
+            }
+
+            if let Some(combined) = &args.combined {
+                let documents = state
+                    .documents
+                    .iter()
+                    .filter_map(|(file, ast)| Some((file.as_path(), ast.as_ref()?)));
+                write_combined(documents, layout, combined, args.pretty)?;
+            }
+        }
+
+        let update = Update {
+            parsed: changed.len(),
+            removed: removed.len(),
+            elapsed: start_time.elapsed(),
+        };
+        render(args, &state, Some(update), collision)?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the files in scope that are affected by a batch of events
+///
+/// Access events are ignored, since reading a file to re-parse it would
+/// trigger another update. Event paths are matched against the collected
+/// files both as reported and canonicalized, since watchers may report
+/// absolute paths.
+pub fn changed_files(files: &[PathBuf], events: &[DebouncedEvent]) -> Vec<PathBuf> {
+    let by_canonical: HashMap<PathBuf, &PathBuf> = files
+        .iter()
+        .filter_map(|file| Some((fs::canonicalize(file).ok()?, file)))
+        .collect();
+
+    let mut changed: Vec<PathBuf> = events
+        .iter()
+        .filter(|event| !matches!(event.kind, EventKind::Access(_)))
+        .flat_map(|event| event.paths.iter())
+        .filter_map(|path| {
+            if files.contains(path) {
+                return Some(path.clone());
+            }
+            let canonical = fs::canonicalize(path).ok()?;
+            by_canonical.get(&canonical).map(|file| (*file).clone())
+        })
+        .collect();
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+/// Summary of a re-parse
+struct Update {
+    /// Number of re-parsed files
+    parsed: usize,
+    /// Number of files that were removed
+    removed: usize,
+    /// Time spent re-parsing
+    elapsed: Duration,
+}
+
+/// Prints the compact diagnostics view
+///
+/// Every warning and failure is shown on a single `file:line:column` line
+/// followed by its help text, so the view stays readable while iterating.
+fn render(
+    args: &ParseArgs,
+    state: &WatchState,
+    update: Option<Update>,
+    collision: Option<Report>,
+) -> Result<()> {
+    let mut output = String::new();
+    if args.clear {
+        output.push_str("\x1B[2J\x1B[H");
+    }
+
+    if let Some(update) = update {
+        let mut summary = format!(
+            "🔄 Re-parsed {} files in {}ms",
+            update.parsed,
+            update.elapsed.as_millis()
+        );
+        if update.removed > 0 {
+            summary.push_str(&format!(", removed {}", update.removed));
+        }
+        writeln!(output, "{}", summary.bright_black()).unwrap();
+    }
+
+    if let Some(collision) = collision {
+        writeln!(output, "{} {}", "✖".red(), collision.to_string().red()).unwrap();
+    }
+
+    let warnings = state
+        .warnings
+        .iter()
+        .flat_map(|(file, warnings)| warnings.iter().map(move |warning| (file, warning)));
+    for (file, warning) in warnings {
+        write_diagnostic(&mut output, "⚠".yellow(), file, warning);
+    }
+    for (file, error) in &state.failures {
+        write_diagnostic(&mut output, "✖".red(), file, error);
+    }
+
+    writeln!(
+        output,
+        "{} {} {} {} {} {} {}",
+        "👀 Watching".bright_green(),
+        args.input.display_paths().underline(),
+        "•".bright_black(),
+        format!("succeeded: {}", state.documents.len()).green(),
+        "•".bright_black(),
+        format!("failed: {}", state.failures.len()).red(),
+        "(Ctrl-C to stop)".bright_black()
+    )
+    .unwrap();
+    write_stdout(output)
+}
+
+/// Writes a diagnostic on a single `file:line:column` line followed by its help text
+fn write_diagnostic(output: &mut String, marker: ColoredString, file: &Path, report: &Report) {
+    let failure = Failure::from_report(file, report);
+    let location = match (failure.line, failure.column) {
+        (Some(line), Some(column)) => format!("{}:{}:{}", file.display(), line, column),
+        _ => file.display().to_string(),
+    };
+    let code = failure
+        .code
+        .map(|code| format!(" [{}]", code))
+        .unwrap_or_default();
+    writeln!(
+        output,
+        "{} {} {}{}",
+        marker,
+        location.underline(),
+        failure.message,
+        code.bright_black()
+    )
+    .unwrap();
+    if let Some(help) = failure.help {
+        writeln!(output, "    {} {}", "help:".cyan(), help).unwrap();
+    }
+}
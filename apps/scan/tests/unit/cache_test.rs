@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::Path;
+
+use rico::parser::Parser;
+use rico_scan::cache::{Cache, Entry};
+use rico_scan::rules::Rule;
+
+const CONTENT: &str = "struct User { 1: string name }";
+
+/// Runs the cache once, storing `file` if it is not fresh
+/// Returns whether the file was fresh
+fn run(dir: &Path, file: &Path, entry: Entry) -> bool {
+    let cache = Cache::open(dir);
+    let fresh = cache.is_fresh(file, &entry);
+    if !fresh {
+        cache.store(file, entry, None);
+    }
+    cache.save();
+    fresh
+}
+
+#[test]
+fn test_cache_hit_and_content_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let file = dir.path().join("user.thrift");
+    fs::write(&file, CONTENT).unwrap();
+
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+    assert!(run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+
+    // Changed content, output settings, rules or strict mode miss the cache
+    let changed = "struct User { 1: string name, 2: i32 age }";
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(changed, None, false, &[], false)
+    ));
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(changed, None, true, &[], false)
+    ));
+    let rules = [Rule::DuplicateFieldId];
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(changed, None, true, &rules, false)
+    ));
+    assert!(run(
+        &cache_dir,
+        &file,
+        Entry::new(changed, None, true, &rules, false)
+    ));
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(changed, None, true, &rules, true)
+    ));
+}
+
+#[test]
+fn test_cache_missing_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let file = dir.path().join("user.thrift");
+    let output = dir.path().join("user.json");
+    fs::write(&file, CONTENT).unwrap();
+    fs::write(&output, "{}").unwrap();
+
+    let entry = || Entry::new(CONTENT, Some(output.clone()), false, &[], false);
+    assert!(!run(&cache_dir, &file, entry()));
+    assert!(run(&cache_dir, &file, entry()));
+
+    fs::remove_file(&output).unwrap();
+    assert!(!run(&cache_dir, &file, entry()));
+}
+
+#[test]
+fn test_cache_version_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let file = dir.path().join("user.thrift");
+    fs::write(&file, CONTENT).unwrap();
+
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+    let index_path = cache_dir.join("index.json");
+    let index = fs::read_to_string(&index_path).unwrap();
+    let version = format!("\"version\":\"{}\"", rico::VERSION);
+    assert!(index.contains(&version), "{}", index);
+
+    // An index written by another rico version is discarded
+    fs::write(
+        &index_path,
+        index.replace(&version, "\"version\":\"0.0.0\""),
+    )
+    .unwrap();
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+    assert!(run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+
+    // So is an index that cannot be read
+    fs::write(&index_path, "not json").unwrap();
+    assert!(!run(
+        &cache_dir,
+        &file,
+        Entry::new(CONTENT, None, false, &[], false)
+    ));
+}
+
+#[test]
+fn test_cache_ast() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let file = dir.path().join("user.thrift");
+    fs::write(&file, CONTENT).unwrap();
+    let ast = Parser::new(CONTENT).parse().unwrap();
+
+    let entry = Entry::new(CONTENT, None, false, &[], false);
+    let cache = Cache::open(&cache_dir);
+    cache.store(&file, entry.clone(), Some(&ast));
+    cache.save();
+
+    let cache = Cache::open(&cache_dir);
+    assert!(cache.is_fresh(&file, &entry));
+    let loaded = cache.load_ast(&entry.hash).unwrap();
+    assert_eq!(
+        serde_json::to_value(&loaded).unwrap(),
+        serde_json::to_value(&ast).unwrap()
+    );
+    cache.save();
+
+    // ASTs of deleted files are removed with their entries
+    fs::remove_file(&file).unwrap();
+    Cache::open(&cache_dir).save();
+    assert!(Cache::open(&cache_dir).load_ast(&entry.hash).is_none());
+}
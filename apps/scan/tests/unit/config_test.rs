@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use rico_scan::commands::fmt::FmtArgs;
+use rico_scan::commands::parse::ParseArgs;
+use rico_scan::config::Config;
+use rico_scan::rules::Rule;
+
+#[derive(Parser)]
+struct ParseCli {
+    #[command(flatten)]
+    args: ParseArgs,
+}
+
+#[derive(Parser)]
+struct FmtCli {
+    #[command(flatten)]
+    args: FmtArgs,
+}
+
+fn config(toml: &str) -> Config {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rico.toml");
+    fs::write(&path, toml).unwrap();
+    Config::load(Some(&path)).unwrap()
+}
+
+fn parse_args(args: &[&str], config: &Config) -> ParseArgs {
+    let mut args = ParseCli::try_parse_from(["parse"].iter().chain(args))
+        .unwrap()
+        .args;
+    args.merge(config).unwrap();
+    args
+}
+
+fn fmt_args(args: &[&str], config: &Config) -> FmtArgs {
+    let mut args = FmtCli::try_parse_from(["fmt"].iter().chain(args))
+        .unwrap()
+        .args;
+    args.input.merge(&config.input).unwrap();
+    args.writer.merge(&config.format);
+    args
+}
+
+#[test]
+fn test_load_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rico.toml");
+    fs::write(
+        &path,
+        r#"
+[input]
+paths = ["idl", "/abs"]
+encoding = "gbk"
+
+[output]
+dir = "build"
+layout = "flat"
+
+[validation]
+rules = ["duplicate-field-id", "unresolved-include"]
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(Some(&path)).unwrap();
+    assert_eq!(config.input.paths, [dir.path().join("idl"), "/abs".into()]);
+    assert_eq!(config.output.dir, Some(dir.path().join("build")));
+    assert_eq!(
+        config.output.cache_dir,
+        Some(dir.path().join(".rico-cache"))
+    );
+    assert_eq!(
+        config.validation.rules,
+        [Rule::DuplicateFieldId, Rule::UnresolvedInclude]
+    );
+
+    fs::write(&path, "[input]\npath = [\"idl\"]\n").unwrap();
+    let error = Config::load(Some(&path)).unwrap_err();
+    assert_eq!(
+        error.code().unwrap().to_string(),
+        "rico_scan::invalid_config"
+    );
+}
+
+#[test]
+fn test_merge_values() {
+    let config = config(
+        r#"
+[input]
+paths = ["idl"]
+exclude = ["vendor"]
+encoding = "gbk"
+
+[output]
+dir = "build"
+
+[validation]
+rules = ["missing-field-id"]
+"#,
+    );
+
+    let args = parse_args(&[], &config);
+    assert_eq!(args.input.paths, config.input.paths);
+    assert_eq!(args.input.exclude, ["vendor"]);
+    assert_eq!(args.input.encoding.unwrap().name(), "GBK");
+    assert_eq!(args.output, config.output.dir);
+    assert_eq!(args.rules, [Rule::MissingFieldId]);
+
+    let args = parse_args(
+        &[
+            "-p",
+            "src",
+            "--exclude",
+            "gen",
+            "--encoding",
+            "latin1",
+            "-o",
+            "out",
+            "--rule",
+            "duplicate-definition",
+        ],
+        &config,
+    );
+    assert_eq!(args.input.paths, [PathBuf::from("src")]);
+    assert_eq!(args.input.exclude, ["gen"]);
+    assert_eq!(args.input.encoding.unwrap().name(), "windows-1252");
+    assert_eq!(args.output, Some("out".into()));
+    assert_eq!(args.rules, [Rule::DuplicateDefinition]);
+}
+
+#[test]
+fn test_merge_flags() {
+    let empty = config("[input]\npaths = [\"idl\"]\n");
+    let enabled = config(
+        r#"
+[input]
+paths = ["idl"]
+respect_ignore_files = false
+
+[output]
+pretty = true
+
+[format]
+trailing_comma = true
+canonical = true
+
+[validation]
+strict = true
+"#,
+    );
+
+    // Defaults
+    let args = parse_args(&[], &empty);
+    assert!(!args.input.no_ignore);
+    assert!(!args.pretty);
+    assert!(!args.strict);
+    let args = fmt_args(&[], &empty);
+    assert!(!args.writer.trailing_comma);
+    assert!(!args.writer.canonical);
+
+    // The configuration overrides the defaults
+    let args = parse_args(&[], &enabled);
+    assert!(args.input.no_ignore);
+    assert!(args.pretty);
+    assert!(args.strict);
+    let args = fmt_args(&[], &enabled);
+    assert!(args.writer.trailing_comma);
+    assert!(args.writer.canonical);
+
+    // Flags override the configuration in both directions
+    let args = parse_args(
+        &["--respect-ignore", "--no-pretty", "--no-strict"],
+        &enabled,
+    );
+    assert!(!args.input.no_ignore);
+    assert!(!args.pretty);
+    assert!(!args.strict);
+    let args = fmt_args(&["--no-trailing-comma", "--no-canonical"], &enabled);
+    assert!(!args.writer.trailing_comma);
+    assert!(!args.writer.canonical);
+
+    let args = parse_args(&["--no-ignore", "--pretty", "--strict"], &empty);
+    assert!(args.input.no_ignore);
+    assert!(args.pretty);
+    assert!(args.strict);
+    let args = fmt_args(&["--trailing-comma", "--canonical"], &empty);
+    assert!(args.writer.trailing_comma);
+    assert!(args.writer.canonical);
+
+    // The last of a flag and its negation wins
+    let args = parse_args(&["--pretty", "--no-pretty"], &empty);
+    assert!(!args.pretty);
+    let args = parse_args(&["--no-ignore", "--respect-ignore"], &enabled);
+    assert!(!args.input.no_ignore);
+    let args = fmt_args(&["--no-canonical", "--canonical"], &enabled);
+    assert!(args.writer.canonical);
+}
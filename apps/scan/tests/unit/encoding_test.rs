@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use rico_scan::encoding::{decode, parse_label, Encoding};
+
+const NAME: &str = "test.thrift";
+
+/// Decodes `bytes` and checks that encoding the text again restores them
+fn round_trip(bytes: &[u8], encoding: Option<&'static Encoding>) -> (String, &'static str, bool) {
+    let decoded = decode(bytes, encoding, Path::new(NAME)).unwrap();
+    assert_eq!(decoded.encode(&decoded.text), bytes);
+    (decoded.text, decoded.encoding.name(), decoded.bom)
+}
+
+#[test]
+fn test_round_trip_utf8() {
+    let text = "// 中文\nstruct A {}\n";
+    assert_eq!(
+        round_trip(text.as_bytes(), None),
+        (text.to_string(), "UTF-8", false)
+    );
+
+    let mut bytes = b"\xEF\xBB\xBF".to_vec();
+    bytes.extend_from_slice(text.as_bytes());
+    assert_eq!(round_trip(&bytes, None), (text.to_string(), "UTF-8", true));
+}
+
+#[test]
+fn test_round_trip_utf16() {
+    let text = "// 中文\nstruct A {}\n";
+    let mut le = b"\xFF\xFE".to_vec();
+    le.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    assert_eq!(round_trip(&le, None), (text.to_string(), "UTF-16LE", true));
+
+    let mut be = b"\xFE\xFF".to_vec();
+    be.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+    assert_eq!(round_trip(&be, None), (text.to_string(), "UTF-16BE", true));
+
+    // The byte order mark takes precedence over the given encoding
+    let gbk = parse_label("gbk").unwrap();
+    assert_eq!(round_trip(&le, Some(gbk)).1, "UTF-16LE");
+}
+
+#[test]
+fn test_round_trip_gbk() {
+    // `// 中文注释` followed by a struct, encoded as GBK
+    let mut bytes = b"// \xd6\xd0\xce\xc4\xd7\xa2\xca\xcd\n".to_vec();
+    bytes.extend_from_slice(b"struct A { 1: string name }\n");
+    let expected = "// 中文注释\nstruct A { 1: string name }\n".to_string();
+
+    assert_eq!(round_trip(&bytes, None), (expected.clone(), "GBK", false));
+    let gbk = parse_label("gbk").unwrap();
+    assert_eq!(round_trip(&bytes, Some(gbk)), (expected, "GBK", false));
+}
+
+#[test]
+fn test_round_trip_latin1() {
+    let bytes = b"// caf\xe9\nstruct A {}\n";
+    let latin1 = parse_label("latin1").unwrap();
+    assert_eq!(
+        round_trip(bytes, Some(latin1)),
+        ("// café\nstruct A {}\n".to_string(), "windows-1252", false)
+    );
+}
+
+#[test]
+fn test_decode_errors() {
+    let utf8 = parse_label("utf-8").unwrap();
+    let error = decode(b"// \xff\n", Some(utf8), Path::new(NAME)).unwrap_err();
+    assert_eq!(
+        error.code().unwrap().to_string(),
+        "rico_scan::invalid_encoding"
+    );
+    assert!(error.to_string().contains(NAME), "{}", error);
+
+    assert_eq!(
+        parse_label("klingon").unwrap_err(),
+        "unknown encoding `klingon`"
+    );
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rico_scan::files::{collect_files, Layout, OutputLayout, WalkOptions};
+
+fn touch(root: &Path, relative: &str) -> PathBuf {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "").unwrap();
+    path
+}
+
+#[test]
+fn test_layout_output_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("idl");
+    let user = touch(&root, "user/user.thrift");
+    let output = Path::new("out");
+
+    let mirror = Layout::new(std::slice::from_ref(&root), OutputLayout::Mirror);
+    assert_eq!(mirror.relative(&user), Path::new("user/user.thrift"));
+    assert_eq!(mirror.key(&user), "user/user.thrift");
+    assert_eq!(
+        mirror.output_path(output, &user, "json"),
+        Path::new("out/user/user.json")
+    );
+
+    let flat = Layout::new(&[root], OutputLayout::Flat);
+    assert_eq!(
+        flat.output_path(output, &user, "json"),
+        Path::new("out/user.json")
+    );
+
+    let single = Layout::new(std::slice::from_ref(&user), OutputLayout::Mirror);
+    assert_eq!(single.key(&user), "user.thrift");
+}
+
+#[test]
+fn test_layout_output_collisions() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("idl");
+    let files = vec![
+        touch(&root, "a/user.thrift"),
+        touch(&root, "b/user.thrift"),
+        touch(&root, "b/User.json.thrift"),
+    ];
+    let output = Path::new("out");
+
+    let mirror = Layout::new(std::slice::from_ref(&root), OutputLayout::Mirror);
+    assert!(mirror
+        .check_output_collisions(&files, output, "json")
+        .is_ok());
+
+    let flat = Layout::new(std::slice::from_ref(&root), OutputLayout::Flat);
+    let error = flat
+        .check_output_collisions(&files, output, "json")
+        .unwrap_err()
+        .to_string();
+    assert!(error.starts_with("Output collision:"), "{}", error);
+    assert!(error.contains("out/user.json"), "{}", error);
+
+    // Paths that only differ in case collide on case-insensitive file systems
+    let files = vec![
+        touch(&root, "c/Order.thrift"),
+        touch(&root, "c/order.thrift"),
+    ];
+    assert!(mirror
+        .check_output_collisions(&files, output, "json")
+        .is_err());
+}
+
+#[test]
+fn test_collect_files_with_globs() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    touch(root, "a.thrift");
+    touch(root, "nested/b.thrift");
+    touch(root, "nested/skip/c.thrift");
+    touch(root, "notes.txt");
+
+    let mut options = WalkOptions {
+        extensions: vec!["thrift".to_string()],
+        ..Default::default()
+    };
+    let relative = |files: Vec<PathBuf>| -> Vec<String> {
+        files
+            .iter()
+            .map(|file| file.strip_prefix(root).unwrap().display().to_string())
+            .collect()
+    };
+
+    let files = collect_files(root, &options).unwrap();
+    assert_eq!(
+        relative(files),
+        ["a.thrift", "nested/b.thrift", "nested/skip/c.thrift"]
+    );
+
+    options.exclude = vec!["nested/skip".to_string()];
+    let files = collect_files(root, &options).unwrap();
+    assert_eq!(relative(files), ["a.thrift", "nested/b.thrift"]);
+
+    options.include = vec!["nested/**".to_string()];
+    let files = collect_files(root, &options).unwrap();
+    assert_eq!(relative(files), ["nested/b.thrift"]);
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use rico::writer::WriterOptions;
+use rico_scan::commands::fmt::{check_comments, process_file};
+use rico_scan::encoding::parse_label;
+
+#[test]
+fn test_check_comments() {
+    let path = Path::new("user.thrift");
+    let kept = "/* users\n * of the app */\nstruct User {\n  // id\n  1: i32 id,\n}\n";
+    let reindented = "/* users\n   * of the app */\nstruct User {\n    // id\n    1: i32 id\n}\n";
+    assert!(check_comments(kept, reindented, path).is_ok());
+
+    for content in [
+        "struct User {1: i32 id} // trailing\n",
+        "struct User {1: i32 id}\n# end of file\n",
+        "struct User {\n  1: i32 id\n  // before the brace\n}\n",
+        "struct User {\n  1: i32 id, // same line, last field\n}\n",
+    ] {
+        let formatted = rico::format::format(content, WriterOptions::default()).unwrap();
+        let error = check_comments(content, &formatted, path).unwrap_err();
+        assert_eq!(
+            error.code().unwrap().to_string(),
+            "rico_scan::comment_dropped"
+        );
+        let label = error.labels().unwrap().next().unwrap();
+        let comment = &content[label.offset()..label.offset() + label.len()];
+        assert!(comment.starts_with("//") || comment.starts_with('#'));
+    }
+}
+
+#[test]
+fn test_fmt_keeps_files_that_would_lose_comments() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = WriterOptions::default();
+
+    let path = dir.path().join("trailing.thrift");
+    fs::write(&path, "struct A {1: i32 a} // keep me\n").unwrap();
+    assert!(process_file(&path, &options, None, false).is_err());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "struct A {1: i32 a} // keep me\n"
+    );
+
+    let gbk = parse_label("gbk").unwrap();
+    let path = dir.path().join("gbk.thrift");
+    let original = gbk
+        .encode("struct A {\n  1: i32 a, // 中文\n}\n")
+        .0
+        .into_owned();
+    fs::write(&path, &original).unwrap();
+    assert!(process_file(&path, &options, Some(gbk), false).is_err());
+    assert_eq!(fs::read(&path).unwrap(), original);
+
+    let path = dir.path().join("formatted.thrift");
+    fs::write(&path, "// kept\nstruct A {1: i32 a}\n").unwrap();
+    let diff = process_file(&path, &options, None, false).unwrap();
+    assert!(!diff.is_empty());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "// kept\nstruct A {\n  1: i32 a,\n}\n\n"
+    );
+}
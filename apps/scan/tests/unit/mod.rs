@@ -0,0 +1,10 @@
+mod cache_test;
+mod config_test;
+mod encoding_test;
+mod files_test;
+mod fmt_test;
+mod parse_test;
+mod report_test;
+mod rules_test;
+mod stats_test;
+mod watch_test;
@@ -0,0 +1,51 @@
+use std::fs;
+
+use clap::Parser;
+use rico_scan::commands::parse::{process_file, ParseArgs};
+use rico_scan::config::Config;
+
+#[derive(Parser)]
+struct ParseCli {
+    #[command(flatten)]
+    args: ParseArgs,
+}
+
+/// Parses `input` with the given flags and returns the diagnostic codes of
+/// the warnings
+fn warnings(input: &str, flags: &[&str]) -> Vec<String> {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("test.thrift");
+    fs::write(&file, input).unwrap();
+
+    let path = file.to_str().unwrap();
+    let mut args = ParseCli::try_parse_from(["parse", path].iter().chain(flags))
+        .unwrap()
+        .args;
+    args.merge(&Config::default()).unwrap();
+    let layout = args.layout();
+    process_file(&file, &args, &layout)
+        .unwrap()
+        .warnings
+        .iter()
+        .map(|warning| warning.code().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_parse_warnings() {
+    let keyword = "struct Blob { 1: binary binary }";
+    let escape = r#"struct User { 1: string id (go.tag = "validate:\"regexp=^\d+$\"") }"#;
+
+    // Keywords used as names are only reported in strict mode
+    assert!(warnings(keyword, &[]).is_empty());
+    assert!(warnings(keyword, &["--no-strict"]).is_empty());
+    assert_eq!(
+        warnings(keyword, &["--strict"]),
+        ["rico::parser::keyword_as_name"]
+    );
+
+    // Invalid escapes are always reported, and never fail the file
+    for flags in [&[][..], &["--strict"]] {
+        assert_eq!(warnings(escape, flags), ["rico::parser::invalid_escape"]);
+    }
+}
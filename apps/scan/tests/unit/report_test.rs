@@ -0,0 +1,203 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rico::Parser;
+use rico_scan::files::{read_input, with_source};
+use rico_scan::report::{escape_xml, Failure, ReportFormat, ScanReport};
+use serde_json::Value;
+
+fn failure(file: &str) -> Failure {
+    Failure {
+        file: PathBuf::from(file),
+        code: Some("rico::parser::unexpected_token".to_string()),
+        message: "Unexpected token".to_string(),
+        help: Some("Expected a different token here".to_string()),
+        line: Some(2),
+        column: Some(5),
+        length: Some(1),
+    }
+}
+
+fn warning(file: &str) -> Failure {
+    Failure {
+        code: Some("rico::parser::invalid_escape".to_string()),
+        message: "Invalid escape sequence".to_string(),
+        help: None,
+        ..failure(file)
+    }
+}
+
+fn render(files: &[PathBuf], failures: &[Failure], format: ReportFormat) -> String {
+    render_with_warnings(files, failures, &[], format)
+}
+
+fn render_with_warnings(
+    files: &[PathBuf],
+    failures: &[Failure],
+    warnings: &[Failure],
+    format: ReportFormat,
+) -> String {
+    ScanReport {
+        files,
+        failures,
+        warnings,
+        cached: Some(1),
+        elapsed: 0.5,
+    }
+    .render(format)
+}
+
+#[test]
+fn test_render_json() {
+    let files = [PathBuf::from("a.thrift"), PathBuf::from("b.thrift")];
+    let report: Value =
+        serde_json::from_str(&render(&files, &[failure("b.thrift")], ReportFormat::Json)).unwrap();
+
+    assert_eq!(report["summary"]["total"], 2);
+    assert_eq!(report["summary"]["succeeded"], 1);
+    assert_eq!(report["summary"]["failed"], 1);
+    assert_eq!(report["summary"]["cached"], 1);
+    assert_eq!(report["failures"][0]["file"], "b.thrift");
+    assert_eq!(
+        report["failures"][0]["code"],
+        "rico::parser::unexpected_token"
+    );
+    assert_eq!(report["failures"][0]["line"], 2);
+    assert_eq!(report["failures"][0]["column"], 5);
+}
+
+#[test]
+fn test_render_sarif() {
+    let files = [PathBuf::from("a.thrift"), PathBuf::from(r"dir\b.thrift")];
+    let failures = [failure("a.thrift"), failure(r"dir\b.thrift")];
+    let report: Value =
+        serde_json::from_str(&render(&files, &failures, ReportFormat::Sarif)).unwrap();
+
+    assert_eq!(report["version"], "2.1.0");
+    let run = &report["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rico-scan");
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["id"], "rico::parser::unexpected_token");
+
+    let result = &run["results"][1];
+    assert_eq!(result["ruleId"], "rico::parser::unexpected_token");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["message"]["text"],
+        "Unexpected token: Expected a different token here"
+    );
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "dir/b.thrift");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 5);
+}
+
+#[test]
+fn test_render_junit() {
+    let files = [PathBuf::from("a.thrift"), PathBuf::from("<b>.thrift")];
+    let mut failure = failure("<b>.thrift");
+    failure.message = r#"Invalid "value" & more"#.to_string();
+    failure.help = None;
+
+    let report = render(&files, &[failure], ReportFormat::Junit);
+    assert_eq!(
+        report,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1" time="0.500">
+  <testsuite name="rico-scan" tests="2" failures="1" time="0.500">
+    <testcase name="a.thrift" classname="thrift"/>
+    <testcase name="&lt;b&gt;.thrift" classname="thrift">
+      <failure message="Invalid &quot;value&quot; &amp; more" type="rico::parser::unexpected_token">&lt;b&gt;.thrift:2:5: Invalid &quot;value&quot; &amp; more</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+    );
+    assert_eq!(render(&files, &[], ReportFormat::Human), "");
+}
+
+#[test]
+fn test_render_warnings() {
+    let files = [PathBuf::from("a.thrift"), PathBuf::from("b.thrift")];
+    let failures = [failure("b.thrift")];
+    let warnings = [warning("a.thrift")];
+    let render = |format| render_with_warnings(&files, &failures, &warnings, format);
+
+    let json: Value = serde_json::from_str(&render(ReportFormat::Json)).unwrap();
+    assert_eq!(json["summary"]["failed"], 1);
+    assert_eq!(json["summary"]["warnings"], 1);
+    assert_eq!(json["warnings"][0]["code"], "rico::parser::invalid_escape");
+
+    let sarif: Value = serde_json::from_str(&render(ReportFormat::Sarif)).unwrap();
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+    assert_eq!(run["results"][0]["level"], "error");
+    assert_eq!(run["results"][1]["level"], "warning");
+    assert_eq!(run["results"][1]["ruleId"], "rico::parser::invalid_escape");
+
+    // Warnings are attached as output without failing the test case
+    let junit = render(ReportFormat::Junit);
+    assert!(junit.contains(r#"<testsuites tests="2" failures="1""#));
+    assert!(junit.contains(concat!(
+        r#"    <testcase name="a.thrift" classname="thrift">"#,
+        "\n      <system-out>warning: a.thrift:2:5: Invalid escape sequence</system-out>",
+        "\n    </testcase>"
+    )));
+}
+
+#[test]
+fn test_escape_xml() {
+    assert_eq!(escape_xml("plain"), "plain");
+    assert_eq!(
+        escape_xml(r#"<a href="x">'&'</a>"#),
+        "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn test_failure_from_report() {
+    let content = "struct User {\n  1: i32 id\n  2: = name\n}\n";
+    let error = Parser::new(content).parse().unwrap_err();
+    let report = with_source(error, Path::new("user.thrift"), content);
+
+    let failure = Failure::from_report(Path::new("user.thrift"), &report);
+    assert_eq!(failure.file, PathBuf::from("user.thrift"));
+    assert_eq!(
+        failure.code.as_deref(),
+        Some("rico::parser::unsupported_type")
+    );
+    assert_eq!((failure.line, failure.column), (Some(3), Some(6)));
+    assert_eq!(failure.length, Some(1));
+}
+
+#[test]
+fn test_failure_columns_in_gbk_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("user.thrift");
+    // `const string A = "中文" = "名字"` encoded as GBK
+    let mut bytes = b"// \xd6\xd0\xce\xc4\nconst string A = \"\xd6\xd0\xce\xc4\" = \"".to_vec();
+    bytes.extend_from_slice(b"\xc3\xfb\xd7\xd6\"\n");
+    fs::write(&path, bytes).unwrap();
+
+    let decoded = read_input(&path, None).unwrap();
+    assert_eq!(decoded.encoding.name(), "GBK");
+    let error = Parser::new(&decoded.text).parse().unwrap_err();
+    let report = with_source(error, &path, &decoded.text);
+
+    // Columns count the two characters of `中文`, not their six UTF-8 bytes
+    let failure = Failure::from_report(&path, &report);
+    assert_eq!((failure.line, failure.column), (Some(2), Some(23)));
+    assert_eq!(failure.length, Some(1));
+
+    let sarif: Value = serde_json::from_str(&render(
+        std::slice::from_ref(&path),
+        &[failure],
+        ReportFormat::Sarif,
+    ))
+    .unwrap();
+    let run = &sarif["runs"][0];
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+    let region = &run["results"][0]["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startColumn"], 23);
+}
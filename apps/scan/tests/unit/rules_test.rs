@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use rico::ast::{Document, DocumentMembers};
+use rico::parser::Parser;
+use rico_scan::rules::{check, Rule};
+
+/// Returns the diagnostic code of the first violation of `rules`, if any
+fn violation(input: &str, rules: &[Rule]) -> Option<String> {
+    violation_in(input, Path::new("test.thrift"), rules, &[])
+}
+
+fn violation_in(
+    input: &str,
+    path: &Path,
+    rules: &[Rule],
+    search_paths: &[PathBuf],
+) -> Option<String> {
+    let document = Parser::new(input).parse().unwrap();
+    check_document(&document, path, rules, search_paths)
+}
+
+fn check_document(
+    document: &Document,
+    path: &Path,
+    rules: &[Rule],
+    search_paths: &[PathBuf],
+) -> Option<String> {
+    check(document, path, rules, search_paths)
+        .err()
+        .map(|violation| violation.code().unwrap().to_string())
+}
+
+#[test]
+fn test_field_id_rules() {
+    let input = "struct A { 1: i32 a, 1: i32 b }";
+    assert_eq!(violation(input, &[]), None);
+    assert_eq!(
+        violation(input, &[Rule::DuplicateFieldId]).as_deref(),
+        Some("rico_scan::rules::duplicate_field_id")
+    );
+    assert_eq!(violation(input, &[Rule::MissingFieldId]), None);
+
+    let mut document = Parser::new("struct A { 1: i32 a, 2: i32 b }")
+        .parse()
+        .unwrap();
+    let DocumentMembers::Struct(s) = &mut document.members[0] else {
+        unreachable!()
+    };
+    s.members[1].field_id = None;
+    let path = Path::new("test.thrift");
+    assert_eq!(
+        check_document(&document, path, &[Rule::MissingFieldId], &[]).as_deref(),
+        Some("rico_scan::rules::missing_field_id")
+    );
+    assert_eq!(
+        check_document(&document, path, &[Rule::DuplicateFieldId], &[]),
+        None
+    );
+
+    let input = "service S { void f(1: i32 a, 1: i32 b) }";
+    assert_eq!(
+        violation(input, &[Rule::DuplicateFieldId]).as_deref(),
+        Some("rico_scan::rules::duplicate_field_id")
+    );
+    assert_eq!(
+        violation("struct A { 1: i32 a, 2: i32 b }", &[Rule::DuplicateFieldId]),
+        None
+    );
+}
+
+#[test]
+fn test_duplicate_definition_rule() {
+    let rules = [Rule::DuplicateDefinition];
+    assert_eq!(
+        violation("struct A {}\nenum A { X }", &rules).as_deref(),
+        Some("rico_scan::rules::duplicate_definition")
+    );
+    assert_eq!(violation("struct A {}\nstruct B {}", &rules), None);
+}
+
+#[test]
+fn test_unresolved_include_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    let common = dir.path().join("common");
+    fs::create_dir(&common).unwrap();
+    fs::write(dir.path().join("base.thrift"), "").unwrap();
+    fs::write(common.join("shared.thrift"), "").unwrap();
+    let input_path = dir.path().join("user.thrift");
+    let rules = [Rule::UnresolvedInclude];
+
+    let input = "include \"base.thrift\"";
+    assert_eq!(violation_in(input, &input_path, &rules, &[]), None);
+
+    // Include paths are decoded like any other string literal
+    let input = r#"include "b\u0061se.thrift""#;
+    assert_eq!(violation_in(input, &input_path, &rules, &[]), None);
+
+    let input = "include \"shared.thrift\"";
+    assert_eq!(
+        violation_in(input, &input_path, &rules, &[]).as_deref(),
+        Some("rico_scan::rules::unresolved_include")
+    );
+    assert_eq!(violation_in(input, &input_path, &rules, &[common]), None);
+}
@@ -0,0 +1,72 @@
+use rico::parser::Parser;
+use rico_scan::commands::stats::{csv_row, Counts};
+
+fn cells(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|cell| cell.to_string()).collect()
+}
+
+#[test]
+fn test_csv_row() {
+    assert_eq!(csv_row(&cells(&["file", "structs"])), "file,structs\n");
+    assert_eq!(csv_row(&cells(&[])), "\n");
+    assert_eq!(
+        csv_row(&cells(&["a,b.thrift", "say \"hi\"", "plain"])),
+        "\"a,b.thrift\",\"say \"\"hi\"\"\",plain\n"
+    );
+    assert_eq!(
+        csv_row(&cells(&["two\nlines", "cr\r"])),
+        "\"two\nlines\",\"cr\r\"\n"
+    );
+}
+
+#[test]
+fn test_counts() {
+    let input = r#"
+namespace go user
+namespace java user
+namespace go.v2 user
+
+typedef i64 UserId
+const i32 LIMIT = 10
+
+enum Role { ADMIN (deprecated = "true"), USER }
+
+struct User {
+    1: UserId id (go.tag = "json:\"id\""),
+    2: string name (go.tag = "json:\"name\""),
+}
+
+union Key { 1: i64 id, 2: string name }
+
+exception NotFound { 1: string message }
+
+service UserService {
+    User get(1: UserId id (api.path = "id")) throws (1: NotFound e),
+    void ping() (api.get = "/ping"),
+}
+"#;
+    let document = Parser::new(input).parse().unwrap();
+    let counts = Counts::from_document(&document);
+    assert_eq!(counts.structs, 1);
+    assert_eq!(counts.unions, 1);
+    assert_eq!(counts.exceptions, 1);
+    assert_eq!(counts.enums, 1);
+    assert_eq!(counts.services, 1);
+    assert_eq!(counts.functions, 2);
+    assert_eq!(counts.fields, 5);
+    assert_eq!(counts.typedefs, 1);
+    assert_eq!(counts.consts, 1);
+    assert_eq!(
+        serde_json::to_value(&counts.annotations).unwrap(),
+        serde_json::json!({ "api.get": 1, "api.path": 1, "deprecated": 1, "go.tag": 2 })
+    );
+    assert_eq!(
+        serde_json::to_value(&counts.namespaces).unwrap(),
+        serde_json::json!({ "go": 1, "go.v2": 1, "java": 1 })
+    );
+
+    let mut total = counts.clone();
+    total += &counts;
+    assert_eq!(total.fields, 10);
+    assert_eq!(total.annotations["go.tag"], 4);
+}
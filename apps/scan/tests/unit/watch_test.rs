@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use notify_debouncer_full::notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind};
+use notify_debouncer_full::notify::{Event, EventKind};
+use notify_debouncer_full::DebouncedEvent;
+use rico_scan::watch::changed_files;
+
+fn event(kind: EventKind, paths: &[&PathBuf]) -> DebouncedEvent {
+    let event = paths.iter().fold(Event::new(kind), |event, path| {
+        event.add_path((*path).clone())
+    });
+    DebouncedEvent::new(event, Instant::now())
+}
+
+#[test]
+fn test_changed_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.thrift");
+    let b = dir.path().join("b.thrift");
+    let other = dir.path().join("notes.txt");
+    for path in [&a, &b, &other] {
+        fs::write(path, "").unwrap();
+    }
+    let files = vec![a.clone(), b.clone()];
+
+    let events = [
+        event(EventKind::Modify(ModifyKind::Any), &[&b]),
+        event(EventKind::Create(CreateKind::File), &[&a, &other]),
+        event(EventKind::Modify(ModifyKind::Any), &[&a]),
+    ];
+    assert_eq!(changed_files(&files, &events), [a.clone(), b.clone()]);
+
+    // Reading a file to re-parse it must not trigger another update
+    let events = [event(EventKind::Access(AccessKind::Any), &[&a])];
+    assert!(changed_files(&files, &events).is_empty());
+
+    // Removed files are reported as changed, so their output is removed
+    let events = [event(EventKind::Remove(RemoveKind::File), &[&b])];
+    assert_eq!(changed_files(&files, &events), [b]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_changed_files_canonical_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let real = dir.path().join("real");
+    let link = dir.path().join("link");
+    fs::create_dir(&real).unwrap();
+    std::os::unix::fs::symlink(&real, &link).unwrap();
+    fs::write(real.join("a.thrift"), "").unwrap();
+
+    // Files collected through a symlink are matched against the resolved
+    // paths reported by watchers
+    let file = link.join("a.thrift");
+    let files = vec![file.clone()];
+    let reported = fs::canonicalize(real.join("a.thrift")).unwrap();
+    let events = [event(EventKind::Modify(ModifyKind::Any), &[&reported])];
+    assert_eq!(changed_files(&files, &events), [file]);
+}
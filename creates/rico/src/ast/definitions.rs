@@ -7,8 +7,13 @@ use super::types::{Common, NodeType, LOC};
 /// Comments can be either single-line (//) or multi-line (/* */),
 /// and are preserved in the AST for documentation purposes.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Comment {
     /// The type of the node (CommentLine or CommentBlock)
+    #[cfg_attr(
+        feature = "typescript",
+        tsify(type = "\"CommentLine\" | \"CommentBlock\"")
+    )]
     pub kind: NodeType,
     /// The text content of the comment
     pub value: String,
@@ -21,6 +26,7 @@ pub struct Comment {
 /// Annotations provide metadata for Thrift definitions and can be used
 /// to customize code generation or add runtime behavior.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always Annotation)
 #[serde(tag = "kind", rename = "Annotation")]
 pub struct Annotation {
@@ -36,6 +42,7 @@ pub struct Annotation {
 ///
 /// Multiple annotations can be specified in parentheses after a definition.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always Annotations)
 #[serde(tag = "kind", rename = "Annotations")]
 pub struct Annotations {
@@ -49,6 +56,7 @@ pub struct Annotations {
 ///
 /// Collection types can hold multiple values of the same type.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always ListType)
 #[serde(tag = "kind", rename = "ListType")]
 pub struct FieldListType {
@@ -65,6 +73,7 @@ pub struct FieldListType {
 ///
 /// Collection types can hold multiple values of the same type.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always SetType)
 #[serde(tag = "kind", rename = "SetType")]
 pub struct FieldSetType {
@@ -82,6 +91,7 @@ pub struct FieldSetType {
 /// Map types associate keys with values, where both key and value
 /// types can be specified.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always MapType)
 #[serde(tag = "kind", rename = "MapType")]
 pub struct FieldMapType {
@@ -101,6 +111,7 @@ pub struct FieldMapType {
 ///
 /// Used for list literals in constant definitions and default values.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always ConstList)
 #[serde(tag = "kind", rename = "ConstList")]
 pub struct ConstList {
@@ -112,6 +123,7 @@ pub struct ConstList {
 
 /// Represents a key-value pair in a map constant.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always PropertyAssignment)
 #[serde(tag = "kind", rename = "PropertyAssignment")]
 pub struct MapProperty {
@@ -127,12 +139,14 @@ pub struct MapProperty {
 ///
 /// Used for map literals in constant definitions and default values.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always ConstMap)
 #[serde(tag = "kind", rename = "ConstMap")]
 pub struct ConstMap {
     /// The location in the source code
     pub loc: LOC,
     /// The map entries
+    #[cfg_attr(feature = "typescript", tsify(type = "PropertyAssignment[]"))]
     pub properties: Vec<MapProperty>,
 }
 
@@ -140,6 +154,7 @@ pub struct ConstMap {
 ///
 /// Can be a simple value, a list, or a map.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 #[serde(untagged)]
 /// enum will deserialize by order,so we should put the most complex type first
 pub enum FieldInitialValue {
@@ -159,14 +174,15 @@ pub enum FieldInitialValue {
 /// - Maps
 /// - User-defined types
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 #[serde(untagged)]
 pub enum FieldType {
     /// A map type
-    MapType(FieldMapType),
+    MapType(#[cfg_attr(feature = "typescript", tsify(type = "MapType"))] FieldMapType),
     /// A list type
-    ListType(FieldListType),
+    ListType(#[cfg_attr(feature = "typescript", tsify(type = "ListType"))] FieldListType),
     /// A set type
-    SetType(FieldSetType),
+    SetType(#[cfg_attr(feature = "typescript", tsify(type = "SetType"))] FieldSetType),
     /// A base type or user-defined type
     CommonType(Common<String>),
 }
@@ -175,6 +191,7 @@ pub enum FieldType {
 ///
 /// Enum values can optionally be assigned explicit integer values.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Initializer {
     pub kind: NodeType,
     /// The explicit value assigned to the enum member
@@ -187,6 +204,7 @@ pub struct Initializer {
 ///
 /// Each enum member can have an optional explicit value and annotations.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always EnumMember)
 #[serde(tag = "kind", rename = "EnumMember")]
 pub struct EnumMember {
@@ -206,6 +224,7 @@ pub struct EnumMember {
 ///
 /// Fields have an optional field ID, type, and various modifiers.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always FieldDefinition)
 #[serde(tag = "kind", rename = "FieldDefinition")]
 pub struct Field {
@@ -235,6 +254,7 @@ pub struct Field {
 ///
 /// Functions define the methods that can be called on a service.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always FunctionDefinition)
 #[serde(tag = "kind", rename = "FunctionDefinition")]
 pub struct Function {
@@ -246,8 +266,10 @@ pub struct Function {
     #[serde(rename = "returnType")]
     pub return_type: FieldType,
     /// The function parameters
+    #[cfg_attr(feature = "typescript", tsify(type = "FieldDefinition[]"))]
     pub params: Vec<Field>,
     /// Optional exceptions that can be thrown
+    #[cfg_attr(feature = "typescript", tsify(type = "FieldDefinition[] | null"))]
     pub throws: Option<Vec<Field>>,
     /// Optional annotations
     pub annotations: Option<Annotations>,
@@ -262,6 +284,7 @@ pub struct Function {
 /// Namespaces specify the package/module name for generated code in
 /// different target languages.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Namespace {
     /// The location of the namespace declaration in the source code
     pub loc: LOC,
@@ -278,6 +301,7 @@ pub struct Namespace {
 /// Include statements allow splitting Thrift definitions across multiple
 /// files for better organization.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Include {
     /// The location of the include statement in the source code
     pub loc: LOC,
@@ -291,6 +315,7 @@ pub struct Include {
 ///
 /// Constants can be used to define shared values of any type.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Const {
     /// The location in the source code
     pub loc: LOC,
@@ -310,6 +335,7 @@ pub struct Const {
 /// Typedefs create aliases for existing types, which can be used
 /// to provide more meaningful names or documentation.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Typedef {
     /// The location in the source code
     pub loc: LOC,
@@ -326,6 +352,7 @@ pub struct Typedef {
 ///
 /// Enums define a set of named constants that can be used as field types.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Enum {
     /// The location in the source code
     pub loc: LOC,
@@ -344,12 +371,14 @@ pub struct Enum {
 /// Exceptions are similar to structs but are used for error handling
 /// in service methods.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Exception {
     /// The location in the source code
     pub loc: LOC,
     /// The name of the exception
     pub name: Common<String>,
     /// The fields of the exception
+    #[cfg_attr(feature = "typescript", tsify(type = "FieldDefinition[]"))]
     pub members: Vec<Field>,
     /// Associated comments
     pub comments: Vec<Comment>,
@@ -361,12 +390,14 @@ pub struct Exception {
 ///
 /// Structs are the primary way to define complex data types in Thrift.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Struct {
     /// The location in the source code
     pub loc: LOC,
     /// The name of the struct
     pub name: Common<String>,
     /// The fields of the struct
+    #[cfg_attr(feature = "typescript", tsify(type = "FieldDefinition[]"))]
     pub members: Vec<Field>,
     /// Associated comments
     pub comments: Vec<Comment>,
@@ -378,12 +409,14 @@ pub struct Struct {
 ///
 /// Unions are similar to structs but only one field can be set at a time.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Union {
     /// The location in the source code
     pub loc: LOC,
     /// The name of the union
     pub name: Common<String>,
     /// The fields of the union
+    #[cfg_attr(feature = "typescript", tsify(type = "FieldDefinition[]"))]
     pub members: Vec<Field>,
     /// Associated comments
     pub comments: Vec<Comment>,
@@ -396,6 +429,7 @@ pub struct Union {
 /// Services define interfaces that can be implemented by servers
 /// and called by clients.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Service {
     /// The location in the source code
     pub loc: LOC,
@@ -404,6 +438,7 @@ pub struct Service {
     /// Optional parent service (for inheritance)
     pub extends: Option<Common<String>>,
     /// The functions defined in the service
+    #[cfg_attr(feature = "typescript", tsify(type = "FunctionDefinition[]"))]
     pub members: Vec<Function>,
     /// Associated comments
     pub comments: Vec<Comment>,
@@ -416,6 +451,7 @@ pub struct Service {
 /// A document is the root node of the AST and contains all the
 /// top-level definitions.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 /// The type of the node (always ThriftDocument)
 #[serde(tag = "kind", rename = "ThriftDocument")]
 pub struct Document {
@@ -428,6 +464,7 @@ pub struct Document {
 /// Each member can be one of several types of definitions that are
 /// allowed at the document level.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 #[serde(tag = "kind")]
 pub enum DocumentMembers {
     /// A namespace declaration
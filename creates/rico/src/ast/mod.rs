@@ -59,6 +59,11 @@
 //! - Integration with other tools
 //! - Persistence and caching
 //!
+//! With the `typescript` feature, every node also derives TypeScript
+//! declarations that wasm-bindgen emits into the generated `.d.ts` file. The
+//! `kind` tags become string literal types, so `DocumentMembers` and the other
+//! tagged nodes are discriminated unions.
+//!
 //! # Usage
 //!
 //! The AST is typically created by the parser and can be:
@@ -66,8 +71,12 @@
 //! - Transformed for optimization
 //! - Converted to other formats
 //! - Written back to Thrift IDL
+//!
+//! String literals keep their quotes and escapes in `value`; see [`string`]
+//! for their decoded form.
 
 mod definitions;
+pub mod string;
 mod types;
 
 pub use self::definitions::*;
@@ -0,0 +1,211 @@
+//! Decoding and encoding of string literals.
+//!
+//! String literal nodes keep their source text, quotes and escapes included,
+//! in `value`, so documents are written back unchanged. The decoded text is
+//! available in `decoded`. The supported escapes are `\"`, `\'`, `\\`, `\/`,
+//! `\n`, `\r`, `\t`, `\b`, `\f`, `\0` and `\uXXXX`, where surrogate pairs are
+//! written as two consecutive `\u` escapes. Other escape sequences are kept
+//! in the decoded text as they are written.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rico::ast::string::{quote_string, unescape_string_literal};
+//!
+//! assert_eq!(unescape_string_literal(r#"'it\'s é'"#).unwrap(), "it's é");
+//! assert_eq!(quote_string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+//! ```
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// An invalid escape sequence in a string literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeError {
+    /// Byte range of the escape sequence within the literal
+    pub range: Range<usize>,
+}
+
+/// Decodes a quoted string literal, such as `"a\tb"` or `'a\'b'`.
+///
+/// # Errors
+///
+/// Returns the byte range of the first invalid escape sequence, or of the
+/// whole literal if it is not a well-formed literal, see
+/// [`unescape_string_literal_lossy`].
+pub fn unescape_string_literal(literal: &str) -> Result<String, EscapeError> {
+    let (decoded, invalid) = unescape_string_literal_lossy(literal)?;
+    match invalid.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(decoded),
+    }
+}
+
+/// Decodes a quoted string literal, keeping invalid escape sequences as they
+/// are written.
+///
+/// Returns the decoded text and the byte ranges of the invalid escape
+/// sequences, e.g. `\d` in `"^\d+$"` is kept as `\d` and reported.
+///
+/// # Errors
+///
+/// Returns the range of the whole literal if it is not enclosed in matching
+/// quotes, contains the enclosing quote unescaped or ends with a backslash.
+///
+/// # Example
+///
+/// ```rust
+/// use rico::ast::string::unescape_string_literal_lossy;
+///
+/// let (decoded, invalid) = unescape_string_literal_lossy(r#""^\d+\n""#).unwrap();
+/// assert_eq!(decoded, "^\\d+\n");
+/// assert_eq!(invalid[0].range, 2..4);
+/// ```
+pub fn unescape_string_literal_lossy(
+    literal: &str,
+) -> Result<(String, Vec<EscapeError>), EscapeError> {
+    let malformed = EscapeError {
+        range: 0..literal.len(),
+    };
+    let (quote, inner) = ['"', '\'']
+        .into_iter()
+        .find_map(|quote| {
+            literal
+                .strip_prefix(quote)
+                .and_then(|rest| rest.strip_suffix(quote))
+                .map(|inner| (quote, inner))
+        })
+        .ok_or_else(|| malformed.clone())?;
+
+    let mut output = String::with_capacity(inner.len());
+    let mut invalid = Vec::new();
+    let mut chars = inner.char_indices();
+    while let Some((start, c)) = chars.next() {
+        if c == quote {
+            return Err(malformed);
+        }
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let Some((i, escaped)) = chars.next() else {
+            return Err(malformed);
+        };
+        match unescape(inner, i, escaped) {
+            Ok((decoded, len)) => {
+                output.push(decoded);
+                skip(&mut chars, len);
+            }
+            Err(end) => {
+                // Offsets in `inner` are one byte behind the literal because of the quote
+                invalid.push(EscapeError {
+                    range: start + 1..end + 1,
+                });
+                output.push('\\');
+                output.push(escaped);
+            }
+        }
+    }
+    Ok((output, invalid))
+}
+
+/// Decodes the escape sequence whose character after the backslash is
+/// `escaped` at `i` in `inner`.
+///
+/// Returns the decoded character and the number of further ASCII characters
+/// the sequence spans, or the end of the invalid part of the sequence.
+fn unescape(inner: &str, i: usize, escaped: char) -> Result<(char, usize), usize> {
+    let decoded = match escaped {
+        '"' | '\'' | '\\' | '/' => escaped,
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'b' => '\u{8}',
+        'f' => '\u{c}',
+        '0' => '\0',
+        'u' => {
+            let end = i + 1;
+            let high = hex4(inner, end).ok_or(end)?;
+            return match high {
+                0xD800..=0xDBFF => {
+                    let low = inner[end + 4..]
+                        .strip_prefix("\\u")
+                        .and_then(|_| hex4(inner, end + 6))
+                        .filter(|low| (0xDC00..=0xDFFF).contains(low))
+                        .ok_or(end + 4)?;
+                    char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                        .map(|c| (c, 10))
+                        .ok_or(end + 10)
+                }
+                _ => char::from_u32(high).map(|c| (c, 4)).ok_or(end + 4),
+            };
+        }
+        _ => return Err(i + escaped.len_utf8()),
+    };
+    Ok((decoded, 0))
+}
+
+/// Returns the text of a string literal `value` as the writer writes it.
+///
+/// A quoted value is decoded like [`unescape_string_literal_lossy`], keeping
+/// invalid escape sequences as they are written. A value without quotes,
+/// e.g. from builders or transforms, is already the text.
+///
+/// # Errors
+///
+/// Returns the range of the whole value if it is a malformed quoted literal.
+pub fn string_literal_text(value: &str) -> Result<Cow<'_, str>, EscapeError> {
+    if is_quoted(value) {
+        unescape_string_literal_lossy(value).map(|(text, _)| Cow::Owned(text))
+    } else {
+        Ok(Cow::Borrowed(value))
+    }
+}
+
+/// Encodes text as a double-quoted string literal.
+///
+/// Quotes, backslashes and control characters are escaped, all other
+/// characters are written as they are.
+pub fn quote_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            '\0' => output.push_str("\\0"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Returns whether `value` is enclosed in matching quotes.
+pub(crate) fn is_quoted(value: &str) -> bool {
+    value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+}
+
+/// Parses the four hex digits at `start`.
+fn hex4(text: &str, start: usize) -> Option<u32> {
+    let digits = text.get(start..start + 4)?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Skips the given number of ASCII characters.
+fn skip(chars: &mut impl Iterator<Item = (usize, char)>, count: usize) {
+    for _ in 0..count {
+        chars.next();
+    }
+}
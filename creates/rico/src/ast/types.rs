@@ -1,8 +1,11 @@
+use super::string::string_literal_text;
 use crate::lexer::Token;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Span {
     pub line: usize,
     pub column: usize,
@@ -19,15 +22,24 @@ impl Span {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct LOC {
     pub start: Span,
     pub end: Span,
 }
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 pub struct Common<T = String> {
     pub kind: NodeType,
     pub value: T,
     pub loc: LOC,
+    /// The text of a parsed string literal, without quotes and with escape
+    /// sequences decoded. Not set for other nodes. The writer writes `value`;
+    /// [`Writer::try_write`](crate::Writer::try_write) rejects a `decoded`
+    /// text that does not match it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", tsify(optional))]
+    pub decoded: Option<String>,
 }
 
 impl<T: fmt::Display> fmt::Display for Common<T> {
@@ -38,11 +50,97 @@ impl<T: fmt::Display> fmt::Display for Common<T> {
 
 impl<T> Common<T> {
     pub fn new(kind: NodeType, value: T, loc: LOC) -> Self {
-        Self { kind, value, loc }
+        Self {
+            kind,
+            value,
+            loc,
+            decoded: None,
+        }
+    }
+}
+
+impl Common<String> {
+    /// Returns the text of a string literal, such as the path of an include.
+    ///
+    /// This is `decoded` when it is set, otherwise `value` decoded like
+    /// [`string_literal_text`], or `value` itself if it is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::{DocumentMembers, Parser};
+    ///
+    /// let mut document = Parser::new(r#"include "a\u0062.thrift""#).parse().unwrap();
+    /// if let DocumentMembers::Include(include) = &mut document.members[0] {
+    ///     assert_eq!(include.name.text(), "ab.thrift");
+    ///     include.name.decoded = None;
+    ///     assert_eq!(include.name.text(), "ab.thrift");
+    /// }
+    /// ```
+    pub fn text(&self) -> Cow<'_, str> {
+        match &self.decoded {
+            Some(decoded) => Cow::Borrowed(decoded),
+            None => string_literal_text(&self.value).unwrap_or(Cow::Borrowed(&self.value)),
+        }
+    }
+
+    /// Returns the value of an integer or hex literal.
+    ///
+    /// Returns `None` for other nodes and for literals that do not fit in an
+    /// `i64`, the range of Thrift integer constants.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            NodeType::IntegerLiteral | NodeType::HexLiteral => parse_int_literal(&self.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a numeric literal as a float.
+    ///
+    /// Integer and hex literals are converted, like Thrift does for double
+    /// constants. Returns `None` for other nodes and for literals that are out
+    /// of the `f64` range.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind {
+            NodeType::FloatLiteral | NodeType::DoubleConstant => self
+                .value
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite()),
+            NodeType::IntegerLiteral | NodeType::HexLiteral => {
+                self.as_i64().map(|value| value as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses a decimal or hex integer literal with an optional sign, such as
+/// `42`, `-0x1F` or `+7`, returning `None` if it does not fit in an `i64`.
+pub(crate) fn parse_int_literal(literal: &str) -> Option<i64> {
+    let (negative, digits) = match literal.as_bytes().first() {
+        Some(b'-') => (true, &literal[1..]),
+        Some(b'+') => (false, &literal[1..]),
+        _ => (false, literal),
+    };
+    let magnitude = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => digits.parse::<u64>(),
+    }
+    .ok()?;
+
+    if negative {
+        0i64.checked_sub_unsigned(magnitude)
+    } else {
+        i64::try_from(magnitude).ok()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
 #[serde(rename_all = "PascalCase")]
 pub enum NodeType {
     ThriftDocument,
@@ -0,0 +1,267 @@
+//! Line-based diffing used by the format check.
+//!
+//! Implements the linear space variant of the Myers O(ND) difference
+//! algorithm and renders the result as a unified diff with three lines of
+//! context.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Computes the shortest edit script turning `old` into `new`.
+///
+/// Returns one operation per line together with the old and new line indices
+/// it refers to. Lines that occur in only one of the texts can never match, so
+/// they are set aside before searching; a file whose lines all changed is then
+/// diffed in linear time.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<(Op, usize, usize)> {
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for line in old {
+        counts.entry(line).or_default().0 += 1;
+    }
+    for line in new {
+        counts.entry(line).or_default().1 += 1;
+    }
+    let old_kept: Vec<usize> = (0..old.len()).filter(|&i| counts[old[i]].1 > 0).collect();
+    let new_kept: Vec<usize> = (0..new.len()).filter(|&i| counts[new[i]].0 > 0).collect();
+    let old_lines: Vec<&str> = old_kept.iter().map(|&i| old[i]).collect();
+    let new_lines: Vec<&str> = new_kept.iter().map(|&i| new[i]).collect();
+
+    let mut matches = Vec::new();
+    let mut search = Search::new(&old_lines, &new_lines);
+    search.matches(0, old_lines.len(), 0, new_lines.len(), &mut matches);
+
+    // Lines between two matches are deleted, then inserted.
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut x, mut y) = (0, 0);
+    let end = (old.len(), new.len());
+    for (old_index, new_index) in matches
+        .into_iter()
+        .map(|(i, j)| (old_kept[i], new_kept[j]))
+        .chain(std::iter::once(end))
+    {
+        ops.extend((x..old_index).map(|i| (Op::Delete, i, y)));
+        ops.extend((y..new_index).map(|j| (Op::Insert, old_index, j)));
+        if old_index < old.len() {
+            ops.push((Op::Equal, old_index, new_index));
+        }
+        x = old_index + 1;
+        y = new_index + 1;
+    }
+    ops
+}
+
+/// The linear space variant of the Myers O(ND) algorithm, which finds the
+/// middle snake of the shortest edit script and recurses on both halves.
+struct Search<'a> {
+    old: &'a [&'a str],
+    new: &'a [&'a str],
+    /// Furthest reaching x per diagonal of the forward search
+    forward: Vec<usize>,
+    /// Furthest reaching x per diagonal of the backward search, counted from
+    /// the end of both texts
+    backward: Vec<usize>,
+}
+
+impl<'a> Search<'a> {
+    fn new(old: &'a [&'a str], new: &'a [&'a str]) -> Self {
+        let size = old.len() + new.len() + 3;
+        Self {
+            old,
+            new,
+            forward: vec![0; size],
+            backward: vec![0; size],
+        }
+    }
+
+    /// Appends the matching line pairs of `old[x0..x1]` and `new[y0..y1]` to
+    /// `matches`, in order.
+    fn matches(
+        &mut self,
+        mut x0: usize,
+        mut x1: usize,
+        mut y0: usize,
+        mut y1: usize,
+        matches: &mut Vec<(usize, usize)>,
+    ) {
+        while x0 < x1 && y0 < y1 && self.old[x0] == self.new[y0] {
+            matches.push((x0, y0));
+            x0 += 1;
+            y0 += 1;
+        }
+        let mut suffix = 0;
+        while x0 < x1 && y0 < y1 && self.old[x1 - 1] == self.new[y1 - 1] {
+            x1 -= 1;
+            y1 -= 1;
+            suffix += 1;
+        }
+
+        if x0 < x1 && y0 < y1 {
+            let (start_x, start_y, end_x, end_y) = self.middle_snake(x0, x1, y0, y1);
+            self.matches(x0, start_x, y0, start_y, matches);
+            matches.extend((start_x..end_x).zip(start_y..end_y));
+            self.matches(end_x, x1, end_y, y1, matches);
+        }
+
+        matches.extend((0..suffix).map(|i| (x1 + i, y1 + i)));
+    }
+
+    /// Returns the start and end of the snake in the middle of the shortest
+    /// edit script of `old[x0..x1]` and `new[y0..y1]`, which share neither
+    /// their first nor their last line.
+    fn middle_snake(
+        &mut self,
+        x0: usize,
+        x1: usize,
+        y0: usize,
+        y1: usize,
+    ) -> (usize, usize, usize, usize) {
+        let n = (x1 - x0) as isize;
+        let m = (y1 - y0) as isize;
+        let delta = n - m;
+        let odd = delta % 2 != 0;
+        let offset = (n + m + 1) / 2 + 1;
+        let index = |k: isize| (k + offset) as usize;
+        self.forward[index(1)] = 0;
+        self.backward[index(1)] = 0;
+
+        for d in 0..=(n + m + 1) / 2 {
+            for k in (-d..=d).step_by(2) {
+                let from_above =
+                    k == -d || (k != d && self.forward[index(k - 1)] < self.forward[index(k + 1)]);
+                let start = if from_above {
+                    self.forward[index(k + 1)] as isize
+                } else {
+                    self.forward[index(k - 1)] as isize + 1
+                };
+                let (mut x, mut y) = (start, start - k);
+                while x < n && y < m && self.old[x0 + x as usize] == self.new[y0 + y as usize] {
+                    x += 1;
+                    y += 1;
+                }
+                self.forward[index(k)] = x as usize;
+                let reverse = delta - k;
+                if odd && reverse.abs() < d && x + self.backward[index(reverse)] as isize >= n {
+                    return (
+                        x0 + start as usize,
+                        y0 + (start - k) as usize,
+                        x0 + x as usize,
+                        y0 + y as usize,
+                    );
+                }
+            }
+
+            for k in (-d..=d).step_by(2) {
+                let from_above = k == -d
+                    || (k != d && self.backward[index(k - 1)] < self.backward[index(k + 1)]);
+                let start = if from_above {
+                    self.backward[index(k + 1)] as isize
+                } else {
+                    self.backward[index(k - 1)] as isize + 1
+                };
+                let (mut x, mut y) = (start, start - k);
+                while x < n
+                    && y < m
+                    && self.old[x1 - 1 - x as usize] == self.new[y1 - 1 - y as usize]
+                {
+                    x += 1;
+                    y += 1;
+                }
+                self.backward[index(k)] = x as usize;
+                let forward = delta - k;
+                if !odd && forward.abs() <= d && x + self.forward[index(forward)] as isize >= n {
+                    return (
+                        x1 - x as usize,
+                        y1 - y as usize,
+                        x1 - start as usize,
+                        y1 - (start - k) as usize,
+                    );
+                }
+            }
+        }
+
+        unreachable!("the forward and backward searches always meet")
+    }
+}
+
+/// Renders a unified diff between `old` and `new`.
+///
+/// Returns an empty string when both texts are equal.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut output = String::new();
+    writeln!(output, "--- {}", old_label).unwrap();
+    writeln!(output, "+++ {}", new_label).unwrap();
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Equal)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut i = 0;
+    while i < changes.len() {
+        // Group changes whose context windows overlap into one hunk.
+        let start = changes[i].saturating_sub(CONTEXT);
+        let mut end = changes[i];
+        while i < changes.len() && changes[i] <= end + 2 * CONTEXT {
+            end = changes[i];
+            i += 1;
+        }
+        let end = (end + CONTEXT).min(ops.len() - 1);
+        let hunk = &ops[start..=end];
+
+        let old_start = hunk[0].1;
+        let new_start = hunk[0].2;
+        let old_count = hunk.iter().filter(|(op, _, _)| *op != Op::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _, _)| *op != Op::Delete).count();
+        writeln!(
+            output,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        )
+        .unwrap();
+
+        for (op, old_index, new_index) in hunk {
+            let (prefix, line) = match op {
+                Op::Equal => (' ', old_lines[*old_index]),
+                Op::Delete => ('-', old_lines[*old_index]),
+                Op::Insert => ('+', new_lines[*new_index]),
+            };
+            output.push(prefix);
+            output.push_str(line);
+            if !line.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    output
+}
+
+/// Formats a hunk range as `start,count` using 1-based line numbers.
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
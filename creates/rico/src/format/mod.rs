@@ -0,0 +1,142 @@
+//! Source formatting built on top of the parser and the writer.
+//!
+//! This module provides the editor and CI oriented entry points:
+//!
+//! - [`format`]: Rewrites a whole document
+//! - [`format_range`]: Returns minimal text edits for the definitions that overlap
+//!   a byte range, mapped back to the source through `LOC.index`
+//! - [`format_check`]: Returns a unified diff between the source and the writer output
+//!
+//! # Example
+//!
+//! ```rust
+//! use rico::format::{format_range, TextEdit};
+//! use rico::writer::WriterOptions;
+//!
+//! let source = "struct A {\n1: i32 a}\n\nstruct B {\n1: i32 b}\n";
+//! let edits = format_range(source, 0..5, WriterOptions::default()).unwrap();
+//!
+//! assert_eq!(
+//!     edits,
+//!     vec![TextEdit {
+//!         range: 0..20,
+//!         new_text: "struct A {\n  1: i32 a,\n}".to_string(),
+//!     }]
+//! );
+//! ```
+
+mod diff;
+
+pub use self::diff::unified_diff;
+
+use std::ops::Range;
+
+use crate::ast::{Comment, DocumentMembers, LOC};
+use crate::parser::{ParseError, Parser};
+use crate::writer::{Writer, WriterOptions};
+
+/// A replacement of a byte range in the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range in the original source
+    pub range: Range<usize>,
+    /// Text that replaces the range
+    pub new_text: String,
+}
+
+/// Parses `source` and writes it back with the given options.
+///
+/// # Errors
+///
+/// Returns a ParseError if the source cannot be parsed.
+pub fn format(source: &str, options: WriterOptions) -> Result<String, ParseError> {
+    let document = Parser::new(source).parse()?;
+    Ok(Writer::with_options(options).write(&document))
+}
+
+/// Formats only the top-level definitions that overlap `range`.
+///
+/// Each returned edit covers one definition, including its leading comments,
+/// and is only produced when the formatted text differs from the source.
+/// An empty range selects the definition containing that position.
+///
+/// # Errors
+///
+/// Returns a ParseError if the source cannot be parsed.
+pub fn format_range(
+    source: &str,
+    range: Range<usize>,
+    options: WriterOptions,
+) -> Result<Vec<TextEdit>, ParseError> {
+    let document = Parser::new(source).parse()?;
+    let mut writer = Writer::with_options(options);
+    let mut edits = Vec::new();
+
+    for member in &document.members {
+        let member_range = member_range(member);
+        let overlaps = if range.is_empty() {
+            member_range.start <= range.start && range.start <= member_range.end
+        } else {
+            member_range.start < range.end && range.start < member_range.end
+        };
+        if !overlaps {
+            continue;
+        }
+
+        let formatted = writer.write_node(member);
+        let new_text = formatted.trim_end_matches('\n');
+        if source.get(member_range.clone()) != Some(new_text) {
+            edits.push(TextEdit {
+                range: member_range,
+                new_text: new_text.to_string(),
+            });
+        }
+    }
+
+    Ok(edits)
+}
+
+/// Checks whether `source` is formatted with the default writer options.
+///
+/// Returns an empty string when the source is already formatted, and a
+/// unified diff between the source and the writer output otherwise.
+///
+/// # Errors
+///
+/// Returns a ParseError if the source cannot be parsed.
+pub fn format_check(source: &str) -> Result<String, ParseError> {
+    format_check_with(source, WriterOptions::default())
+}
+
+/// Same as [`format_check`], using the given writer options.
+///
+/// # Errors
+///
+/// Returns a ParseError if the source cannot be parsed.
+pub fn format_check_with(source: &str, options: WriterOptions) -> Result<String, ParseError> {
+    let formatted = format(source, options)?;
+    Ok(unified_diff(source, &formatted, "original", "formatted"))
+}
+
+/// Returns the byte range of a definition, including its leading comments.
+fn member_range(member: &DocumentMembers) -> Range<usize> {
+    let (loc, comments) = member_parts(member);
+    let start = comments
+        .first()
+        .map_or(loc.start.index, |comment| comment.loc.start.index);
+    start.min(loc.start.index)..loc.end.index
+}
+
+fn member_parts(member: &DocumentMembers) -> (&LOC, &[Comment]) {
+    match member {
+        DocumentMembers::Namespace(ns) => (&ns.loc, &ns.comments),
+        DocumentMembers::Include(inc) => (&inc.loc, &inc.comments),
+        DocumentMembers::Const(c) => (&c.loc, &c.comments),
+        DocumentMembers::Typedef(td) => (&td.loc, &td.comments),
+        DocumentMembers::Enum(e) => (&e.loc, &e.comments),
+        DocumentMembers::Struct(s) => (&s.loc, &s.comments),
+        DocumentMembers::Union(u) => (&u.loc, &u.comments),
+        DocumentMembers::Exception(e) => (&e.loc, &e.comments),
+        DocumentMembers::Service(s) => (&s.loc, &s.comments),
+    }
+}
@@ -45,8 +45,10 @@
 //! - Token type definitions
 //! - Source location tracking
 //! - Error handling and recovery
-//! - Iterator interface for token stream
+//! - Iterator interface for token stream, see [`tokenize`]
 
+mod stream;
 mod token;
 
+pub use self::stream::{tokenize, Tokens};
 pub use self::token::Token;
@@ -0,0 +1,72 @@
+//! A token stream with text and source locations.
+
+use logos::Logos;
+
+use super::Token;
+use crate::ast::LOC;
+use crate::line_index::{LineIndex, PositionEncoding};
+
+/// Tokenizes Thrift IDL input into tokens with their text and location.
+///
+/// Unlike the parser, the stream keeps comments, and input that matches no
+/// token is returned as [`Token::Error`] instead of ending the stream, so
+/// syntax highlighters and linters see the whole input. Whitespace and line
+/// breaks are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use rico::lexer::{tokenize, Token};
+///
+/// let tokens: Vec<_> = tokenize("// id\ntypedef i64 Id ~")
+///     .map(|(token, text, loc)| (token, text, loc.start.line))
+///     .collect();
+///
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         (Token::LineComment, "// id", 1),
+///         (Token::Typedef, "typedef", 2),
+///         (Token::I64, "i64", 2),
+///         (Token::Identifier, "Id", 2),
+///         (Token::Error, "~", 2),
+///     ]
+/// );
+/// ```
+pub fn tokenize(input: &str) -> Tokens<'_> {
+    Tokens {
+        lexer: Token::lexer(input),
+        line_index: LineIndex::new(input),
+        position_encoding: PositionEncoding::default(),
+    }
+}
+
+/// An iterator over `(Token, text, LOC)`, created by [`tokenize`].
+pub struct Tokens<'a> {
+    lexer: logos::Lexer<'a, Token>,
+    line_index: LineIndex<'a>,
+    position_encoding: PositionEncoding,
+}
+
+impl Tokens<'_> {
+    /// Sets the unit in which the columns of token locations are counted,
+    /// UTF-8 bytes by default.
+    pub fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = (Token, &'a str, LOC);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.lexer.next()?.unwrap_or(Token::Error);
+        let span = self.lexer.span();
+        let loc = LOC {
+            start: self.line_index.position(span.start, self.position_encoding),
+            end: self.line_index.position(span.end, self.position_encoding),
+        };
+        Some((token, self.lexer.slice(), loc))
+    }
+}
@@ -1,17 +1,18 @@
-use logos::{Lexer, Logos, Skip};
-
-/// Update the line count and the char index.
-fn newline_callback(lex: &mut Lexer<Token>) -> Skip {
-    lex.extras.0 += 1;
-    lex.extras.1 = lex.span().end;
-    Skip
-}
+use logos::Logos;
 
+/// Tokens of Thrift IDL.
+///
+/// Positions are not tracked by the lexer; [`tokenize`](crate::lexer::tokenize)
+/// derives the line and column of each token from a
+/// [`LineIndex`](crate::line_index::LineIndex).
+///
+/// New tokens may be added in minor releases, so matches outside of this
+/// crate need a wildcard arm.
 #[derive(Logos, Debug, PartialEq, Clone)]
-#[logos(skip r"[ \t]+")]
-#[logos(extras = (usize, usize))]
+#[logos(skip r"[ \t\f]+")]
+#[non_exhaustive]
 pub enum Token {
-    #[regex(r"[\r\n\f]", newline_callback)]
+    #[regex(r"\r\n?|\n", logos::skip)]
     Newline,
 
     // Keywords
@@ -77,14 +78,14 @@ pub enum Token {
     #[regex(r"[+-]?[0-9]+")]
     IntegerLiteral,
 
-    #[regex(r"[+-]?[0-9]*\.[0-9]+([eE][-+]?[0-9]+)?")]
+    #[regex(r"[+-]?([0-9]*\.[0-9]+([eE][-+]?[0-9]+)?|[0-9]+[eE][-+]?[0-9]+)")]
     DoubleLiteral,
 
     #[token("true")]
     #[token("false")]
     BooleanLiteral,
 
-    #[regex(r"0[xX][0-9a-fA-F]+")]
+    #[regex(r"[+-]?0[xX][0-9a-fA-F]+")]
     HexLiteral,
 
     // Punctuation
@@ -116,11 +117,53 @@ pub enum Token {
     Dot,
 
     // Comments
-    #[regex(r"(//|#).*")]
+    #[regex(r"(//|#)[^\r\n]*")]
     LineComment,
     #[regex(r"/\*[^*]*\*+(?:[^*/][^*]*\*+)*/")]
     BlockComment,
 
     #[token("oneway")]
     Oneway,
+
+    /// Input that matches no other token, only produced by
+    /// [`tokenize`](crate::lexer::tokenize)
+    Error,
+}
+
+impl Token {
+    /// Returns whether the token is a keyword or a base type name.
+    ///
+    /// Keywords are contextual: the parser accepts them wherever a name is
+    /// expected, e.g. a field called `binary` or an enum member called `set`.
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            Token::Namespace
+                | Token::Include
+                | Token::Typedef
+                | Token::Const
+                | Token::Enum
+                | Token::Struct
+                | Token::Union
+                | Token::Exception
+                | Token::Service
+                | Token::Extends
+                | Token::Throws
+                | Token::Required
+                | Token::Optional
+                | Token::Oneway
+                | Token::Void
+                | Token::Bool
+                | Token::Byte
+                | Token::I16
+                | Token::I32
+                | Token::I64
+                | Token::Double
+                | Token::String
+                | Token::Binary
+                | Token::Map
+                | Token::List
+                | Token::Set
+        )
+    }
 }
@@ -28,6 +28,12 @@
 //! - [`writer`]: Converting AST back to Thrift IDL text. Handles proper formatting,
 //!   indentation, and comment preservation.
 //!
+//! - [`format`]: Source formatting for editors and CI. Provides range formatting
+//!   with minimal text edits and a format check that reports a unified diff.
+//!
+//! - [`line_index`]: Conversion between byte offsets and line/column positions
+//!   counted in UTF-8 bytes, UTF-16 code units or characters.
+//!
 //! # Getting Started
 //!
 //! Rico can be used in two main ways:
@@ -127,10 +133,15 @@
 //! 5. Validate AST modifications before writing
 
 pub mod ast;
+pub mod format;
 pub mod lexer;
+pub mod line_index;
 pub mod parser;
 pub mod writer;
 
 pub use ast::*;
 pub use parser::Parser;
 pub use writer::Writer;
+
+/// The version of the rico crate, e.g. to key caches of parse results
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,153 @@
+//! Conversion between byte offsets and line/column positions.
+//!
+//! The parser, the WASM bindings and editor integrations all report positions
+//! in the same source text, but count columns in different units: Rust works
+//! with UTF-8 bytes, JS strings with UTF-16 code units and most terminals with
+//! characters. [`LineIndex`] computes the line starts once and converts offsets
+//! in any of these units, so every consumer agrees on where a line begins.
+//!
+//! Lines end at `\n`, `\r\n` or a lone `\r`. Lines and columns are 1-based like
+//! in [`Span`]; subtract one for 0-based protocols such as LSP.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rico::line_index::{LineIndex, PositionEncoding};
+//!
+//! let source = "const string GREETING = \"héllo\"\nconst i32 ANSWER = 42";
+//! let index = LineIndex::new(source);
+//! let offset = source.find("ANSWER").unwrap();
+//!
+//! let span = index.position(offset, PositionEncoding::Utf16);
+//! assert_eq!((span.line, span.column, span.index), (2, 11, offset));
+//!
+//! // The closing quote follows the two-byte `é`
+//! let quote = source.rfind('"').unwrap();
+//! assert_eq!(index.position(quote, PositionEncoding::Utf8).column, 32);
+//! assert_eq!(index.position(quote, PositionEncoding::Utf16).column, 31);
+//! assert_eq!(index.offset(2, 11, PositionEncoding::Utf16), Some(offset));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::Span;
+
+/// The unit in which columns are counted, named like the LSP
+/// `PositionEncodingKind` when serialized.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "typescript", derive(tsify::Tsify))]
+pub enum PositionEncoding {
+    /// UTF-8 bytes, the offsets of Rust strings
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+    /// UTF-16 code units, the offsets of JS strings and the LSP default
+    #[serde(rename = "utf-16")]
+    Utf16,
+    /// Unicode scalar values, i.e. Rust `char`s
+    #[serde(rename = "utf-32")]
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Returns the length of `text` in this encoding.
+    pub fn len(self, text: &str) -> usize {
+        match self {
+            PositionEncoding::Utf8 => text.len(),
+            PositionEncoding::Utf16 => text.chars().map(char::len_utf16).sum(),
+            PositionEncoding::Utf32 => text.chars().count(),
+        }
+    }
+}
+
+/// The line starts of a source text.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// Byte offset of the first character of every line
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Indexes the lines of `source`.
+    pub fn new(source: &'a str) -> Self {
+        let bytes = source.as_bytes();
+        let mut line_starts = vec![0];
+        for (i, &byte) in bytes.iter().enumerate() {
+            let is_break = match byte {
+                b'\n' => true,
+                b'\r' => bytes.get(i + 1) != Some(&b'\n'),
+                _ => false,
+            };
+            if is_break {
+                line_starts.push(i + 1);
+            }
+        }
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the number of lines.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the 1-based line containing the byte `offset`.
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    /// Returns the text of a 1-based line, without its line break.
+    pub fn line_text(&self, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.source.len());
+        Some(self.source[start..end].trim_end_matches(['\n', '\r']))
+    }
+
+    /// Converts a byte offset into a position whose column is counted in
+    /// `encoding`.
+    ///
+    /// Offsets past the end are clamped to the end of the source, and offsets
+    /// inside a character to the start of that character.
+    pub fn position(&self, offset: usize, encoding: PositionEncoding) -> Span {
+        let offset = self.floor_char_boundary(offset);
+        let line = self.line(offset);
+        let start = self.line_starts[line - 1];
+        let column = encoding.len(&self.source[start..offset]) + 1;
+        Span::new(line, column, offset)
+    }
+
+    /// Converts a 1-based line and column counted in `encoding` back into a
+    /// byte offset.
+    ///
+    /// Returns `None` if the line does not exist. Columns past the end of the
+    /// line are clamped to its end, and columns inside a character to the
+    /// start of that character.
+    pub fn offset(&self, line: usize, column: usize, encoding: PositionEncoding) -> Option<usize> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let text = self.line_text(line)?;
+        let mut remaining = column.saturating_sub(1);
+        for (i, c) in text.char_indices() {
+            let width = encoding.len(c.encode_utf8(&mut [0; 4]));
+            if remaining < width {
+                return Some(start + i);
+            }
+            remaining -= width;
+        }
+        Some(start + text.len())
+    }
+
+    fn floor_char_boundary(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+}
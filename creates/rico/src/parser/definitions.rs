@@ -30,14 +30,9 @@ impl<'a> Parser<'a> {
         let tracker = LocationTracker::new(self.start_pos());
         let comments = self.take_pending_comments();
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingNamespaceScope)?;
-        let scope = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let scope = self.consume_name(ParseErrorKind::MissingNamespaceScope)?;
 
-        self.consume_with_error(
-            Token::Identifier,
-            ParseErrorKind::MissingNamespaceIdentifier,
-        )?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_string());
+        let name = self.consume_name(ParseErrorKind::MissingNamespaceIdentifier)?;
         let end_loc = name.loc;
 
         Ok(Namespace {
@@ -53,8 +48,7 @@ impl<'a> Parser<'a> {
         let comments = self.take_pending_comments();
         let field_type = self.parse_field_type()?;
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingConstIdentifier)?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let name = self.consume_name(ParseErrorKind::MissingConstIdentifier)?;
 
         self.consume(Token::Equals)?;
         let const_value = self.parse_field_value()?;
@@ -75,8 +69,7 @@ impl<'a> Parser<'a> {
         let comments = self.take_pending_comments();
         let field_type = self.parse_field_type()?;
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingTypedefIdentifier)?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_string());
+        let name = self.consume_name(ParseErrorKind::MissingTypedefIdentifier)?;
 
         Ok(Typedef {
             loc: tracker.to_parent_loc(&name.loc),
@@ -90,8 +83,7 @@ impl<'a> Parser<'a> {
         let tracker = LocationTracker::new(self.start_pos());
         let comments = self.take_pending_comments();
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingEnumIdentifier)?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let name = self.consume_name(ParseErrorKind::MissingEnumIdentifier)?;
 
         let members = self.parse_members(|parser| parser.parse_enum_member())?;
         let annotations = self.parse_annotations()?;
@@ -148,9 +140,7 @@ impl<'a> Parser<'a> {
                     break;
                 }
 
-                self.expect_token(Token::Identifier)?;
-                let annotation_name =
-                    create_identifier(self.get_token_loc(), self.text().to_owned());
+                let annotation_name = self.expect_name(ParseErrorKind::UnexpectedToken)?;
 
                 self.consume(Token::Equals)?;
                 self.consume(Token::StringLiteral)?;
@@ -159,6 +149,7 @@ impl<'a> Parser<'a> {
                     kind: NodeType::StringLiteral,
                     value: self.text().to_owned(),
                     loc: value_loc,
+                    decoded: Some(self.decode_string_literal()?),
                 };
 
                 annotations.push(Annotation {
@@ -185,8 +176,7 @@ impl<'a> Parser<'a> {
         let tracker = LocationTracker::new(self.start_pos());
         let comments = self.take_pending_comments();
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingServiceIdentifier)?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let name = self.consume_name(ParseErrorKind::MissingServiceIdentifier)?;
 
         // Parse extends clause if present
         let extends = self.parse_extends()?;
@@ -205,8 +195,7 @@ impl<'a> Parser<'a> {
             // Parse return type
             let return_type = parser.parse_return_type()?;
 
-            parser.consume(Token::Identifier)?;
-            let function_name = create_identifier(parser.get_token_loc(), parser.text().to_owned());
+            let function_name = parser.consume_name(ParseErrorKind::UnexpectedToken)?;
             let function_start_loc = function_name.loc;
 
             // Parse parameters
@@ -281,8 +270,7 @@ impl<'a> Parser<'a> {
         let tracker = LocationTracker::new(self.start_pos());
         let comments = self.take_pending_comments();
 
-        self.consume_with_error(Token::Identifier, ParseErrorKind::MissingStructIdentifier)?;
-        let name = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let name = self.consume_name(ParseErrorKind::MissingStructIdentifier)?;
 
         let members = self.parse_members(|parser| parser.parse_field())?;
 
@@ -301,11 +289,9 @@ impl<'a> Parser<'a> {
     fn parse_extends(&mut self) -> Result<Option<Common<String>>, ParseError> {
         if let Some(Token::Extends) = self.peek() {
             self.advance(); // Consume 'extends'
-            self.consume_with_error(Token::Identifier, ParseErrorKind::MissingServiceExtends)?;
-            Ok(Some(create_identifier(
-                self.get_token_loc(),
-                self.text().to_owned(),
-            )))
+            Ok(Some(
+                self.consume_name(ParseErrorKind::MissingServiceExtends)?,
+            ))
         } else {
             Ok(None)
         }
@@ -343,39 +329,6 @@ impl<'a> Parser<'a> {
         Ok(None)
     }
 
-    fn parse_field_name(&mut self) -> Result<Common<String>, ParseError> {
-        self.advance();
-
-        const VALID_TOKENS: &[Token] = &[
-            Token::Identifier,
-            // adapt keywords, but not recommend to use
-            Token::Namespace,
-            Token::Include,
-            Token::List,
-            Token::Map,
-            Token::Set,
-            Token::Oneway,
-            Token::Required,
-            Token::Optional,
-            Token::Throws,
-            Token::Bool,
-            Token::Extends,
-            Token::Struct,
-            Token::Double,
-            Token::Service,
-            Token::Enum,
-        ];
-
-        if !VALID_TOKENS.iter().any(|valid| self.token() == Some(valid)) {
-            return Err(self.error(ParseErrorKind::InvalidFieldName));
-        }
-
-        Ok(create_identifier(
-            self.get_token_loc(),
-            self.text().to_owned(),
-        ))
-    }
-
     fn parse_field(&mut self) -> Result<Field, ParseError> {
         let field_comments = self.take_pending_comments();
 
@@ -401,7 +354,7 @@ impl<'a> Parser<'a> {
         let field_type = self.parse_field_type()?;
 
         // Parse field name
-        let field_name = self.parse_field_name()?;
+        let field_name = self.consume_name(ParseErrorKind::InvalidFieldName)?;
 
         // Parse default value if present
         let default_value = if let Some(Token::Equals) = self.peek() {
@@ -428,8 +381,7 @@ impl<'a> Parser<'a> {
     fn parse_enum_member(&mut self) -> Result<EnumMember, ParseError> {
         let member_comments = self.take_pending_comments();
 
-        self.expect_token_with_error(Token::Identifier, ParseErrorKind::InvalidEnumMemberName)?;
-        let member_name = create_identifier(self.get_token_loc(), self.text().to_owned());
+        let member_name = self.expect_name(ParseErrorKind::InvalidEnumMemberName)?;
         let member_start_loc = member_name.loc;
 
         let mut initializer = None;
@@ -439,6 +391,7 @@ impl<'a> Parser<'a> {
             let value_token = self.token().unwrap();
 
             if matches!(value_token, Token::IntegerLiteral | Token::HexLiteral) {
+                self.check_numeric_literal()?;
                 let value = Common::new(
                     NodeType::from_token(value_token).unwrap(),
                     self.text().to_owned(),
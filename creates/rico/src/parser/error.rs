@@ -77,7 +77,7 @@ pub enum ParseError {
     #[error("Invalid field name")]
     #[diagnostic(
         code(rico::parser::invalid_field_name),
-        help("Field names must be valid identifiers")
+        help("Field names must be identifiers or keywords")
     )]
     InvalidFieldName {
         #[label("This field name is invalid")]
@@ -184,10 +184,20 @@ pub enum ParseError {
         span: SourceSpan,
     },
 
+    #[error("Numeric literal out of range")]
+    #[diagnostic(
+        code(rico::parser::numeric_overflow),
+        help("Integer constants must fit in an i64 and double constants in an f64")
+    )]
+    NumericOverflow {
+        #[label("This literal is out of range")]
+        span: SourceSpan,
+    },
+
     #[error("Invalid enum member name")]
     #[diagnostic(
         code(rico::parser::invalid_enum_member_name),
-        help("Enum member names must be valid identifiers and cannot be keywords")
+        help("Enum member names must be identifiers or keywords")
     )]
     InvalidEnumMemberName {
         #[label("This enum member name is invalid")]
@@ -195,6 +205,33 @@ pub enum ParseError {
     },
 }
 
+/// Warnings reported by a parser, see [`Parser::warnings`](super::Parser::warnings).
+#[derive(Error, Debug, Diagnostic, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    #[error("Keyword \"{keyword}\" used as a name")]
+    #[diagnostic(
+        code(rico::parser::keyword_as_name),
+        severity(Warning),
+        help("Keywords are accepted as names, but code generators may reject them; consider renaming")
+    )]
+    KeywordAsName {
+        keyword: String,
+        #[label("This name is a keyword")]
+        span: SourceSpan,
+    },
+
+    #[error("Invalid escape sequence")]
+    #[diagnostic(
+        code(rico::parser::invalid_escape),
+        severity(Warning),
+        help("Valid escapes are \\\", \\', \\\\, \\/, \\n, \\r, \\t, \\b, \\f, \\0 and \\uXXXX")
+    )]
+    InvalidEscape {
+        #[label("This escape sequence is kept as written")]
+        span: SourceSpan,
+    },
+}
+
 // Helper function to convert our Span to miette's SourceSpan
 impl ParseError {
     pub(crate) fn from_loc(span: Span, kind: ParseErrorKind) -> Self {
@@ -244,6 +281,7 @@ impl ParseError {
             ParseErrorKind::InvalidEnumMemberName => {
                 Self::InvalidEnumMemberName { span: source_span }
             }
+            ParseErrorKind::NumericOverflow => Self::NumericOverflow { span: source_span },
         }
     }
 }
@@ -269,4 +307,5 @@ pub enum ParseErrorKind {
     MissingServiceIdentifier,
     MissingServiceExtends,
     InvalidEnumMemberName,
+    NumericOverflow,
 }
@@ -13,6 +13,7 @@ pub fn create_keyword_field_type(token: &Token, loc: LOC, slice: &str) -> FieldT
         kind: NodeType::from_token(token).unwrap(),
         loc,
         value: slice.to_string(),
+        decoded: None,
     })
 }
 
@@ -21,6 +22,7 @@ pub fn create_identifier_field_type(loc: LOC, slice: &str) -> FieldType {
         kind: NodeType::Identifier,
         loc,
         value: slice.to_string(),
+        decoded: None,
     })
 }
 
@@ -61,6 +63,16 @@ pub fn create_const_value(token: &Token, loc: LOC, slice: &str) -> FieldInitialV
         kind: NodeType::from_token(token).unwrap(),
         loc,
         value: slice.to_string(),
+        decoded: None,
+    })
+}
+
+pub fn create_string_value(loc: LOC, slice: &str, decoded: String) -> FieldInitialValue {
+    FieldInitialValue::ConstValue(Common {
+        kind: NodeType::StringLiteral,
+        loc,
+        value: slice.to_string(),
+        decoded: Some(decoded),
     })
 }
 
@@ -69,6 +81,7 @@ pub fn create_identifier_value(loc: LOC, slice: &str) -> FieldInitialValue {
         kind: NodeType::Identifier,
         loc,
         value: slice.to_string(),
+        decoded: None,
     })
 }
 
@@ -85,6 +98,7 @@ pub(crate) fn create_identifier(loc: LOC, value: String) -> Common {
         kind: NodeType::Identifier,
         value,
         loc,
+        decoded: None,
     }
 }
 
@@ -113,6 +127,7 @@ pub(crate) fn create_field_id(loc: LOC, value: String) -> Common {
         kind: NodeType::FieldID,
         value,
         loc,
+        decoded: None,
     }
 }
 
@@ -121,5 +136,6 @@ pub fn create_void(loc: LOC, value: String) -> Common<String> {
         kind: NodeType::VoidKeyword,
         value,
         loc,
+        decoded: None,
     }
 }
@@ -91,11 +91,15 @@ mod token;
 mod types;
 mod values;
 
+pub use error::{ParseError, ParseWarning};
+
+use crate::ast::string::unescape_string_literal_lossy;
 use crate::ast::*;
 use crate::lexer::Token;
-use crate::parser::error::ParseError;
+use crate::line_index::{LineIndex, PositionEncoding};
 use error::ParseErrorKind;
 use logos::Logos;
+use miette::SourceSpan;
 
 #[derive(Debug, Clone)]
 pub struct ParserToken<'a> {
@@ -142,6 +146,10 @@ pub struct Parser<'a> {
     next_token: Option<ParserToken<'a>>,
     pending_comments: Vec<Comment>,
     last_span: logos::Span,
+    line_index: LineIndex<'a>,
+    position_encoding: PositionEncoding,
+    strict: bool,
+    warnings: Vec<ParseWarning>,
 }
 
 impl<'a> Parser<'a> {
@@ -155,9 +163,66 @@ impl<'a> Parser<'a> {
             cur_token: None,
             pending_comments: Vec::new(),
             last_span,
+            line_index: LineIndex::new(input),
+            position_encoding: PositionEncoding::default(),
+            strict: false,
+            warnings: Vec::new(),
         }
     }
 
+    /// Sets the unit in which the columns of AST locations are counted.
+    ///
+    /// Columns count UTF-8 bytes by default. Use [`PositionEncoding::Utf16`]
+    /// for JS strings and LSP clients. `index` is always a byte offset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::line_index::PositionEncoding;
+    /// use rico::{DocumentMembers, Parser};
+    ///
+    /// let document = Parser::new("const string NAME = \"名前\"")
+    ///     .with_position_encoding(PositionEncoding::Utf16)
+    ///     .parse()
+    ///     .unwrap();
+    /// if let DocumentMembers::Const(c) = &document.members[0] {
+    ///     assert_eq!(c.loc.end.column, 25);
+    ///     assert_eq!(c.loc.end.index, 28);
+    /// }
+    /// ```
+    pub fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Enables strict mode, which reports keywords used as names.
+    ///
+    /// Keywords such as `binary` or `service` are accepted wherever a name is
+    /// expected. In strict mode each such name is also recorded as a
+    /// [`ParseWarning`], available from [`Parser::warnings`] after parsing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::Parser;
+    ///
+    /// let mut parser = Parser::new("struct Blob { 1: binary binary }").with_strict(true);
+    /// parser.parse().unwrap();
+    /// assert_eq!(parser.warnings().len(), 1);
+    /// ```
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the warnings reported while parsing.
+    ///
+    /// Invalid escape sequences in string literals are always reported, keywords
+    /// used as names only in strict mode.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
     /// Parses the Thrift IDL input and returns a Document AST.
     ///
     /// The Document contains all parsed definitions including:
@@ -277,31 +342,43 @@ impl<'a> Parser<'a> {
     }
 
     fn bind_start_position(&mut self) -> Span {
-        let source = self.lexer.source();
-        let span = self.lexer.span();
-        let start_index = source[..span.start].len();
-        let column = source[self.lexer.extras.1..span.start].len() + 1;
-        let line = self.lexer.extras.0 + 1;
-        let index = start_index;
-        Span::new(line, column, index)
+        self.line_index
+            .position(self.lexer.span().start, self.position_encoding)
     }
 
     fn bind_end_position(&mut self) -> Span {
-        let span = self.lexer.span();
-        let source = self.lexer.source();
-        // handle inner multiple content
-        let newline_count = self.lexer.slice().matches('\n').count();
+        self.line_index
+            .position(self.lexer.span().end, self.position_encoding)
+    }
 
-        if newline_count > 0 {
-            self.lexer.extras.0 += newline_count;
-            self.lexer.extras.1 = self.lexer.span().end;
+    /// Checks that the current numeric literal fits in an `i64` or `f64`.
+    fn check_numeric_literal(&self) -> Result<(), ParseError> {
+        let text = self.text();
+        let in_range = match self.token() {
+            Some(Token::IntegerLiteral | Token::HexLiteral) => parse_int_literal(text).is_some(),
+            Some(Token::DoubleLiteral) => text.parse::<f64>().is_ok_and(f64::is_finite),
+            _ => true,
+        };
+        if in_range {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::NumericOverflow))
         }
+    }
 
-        let line = self.lexer.extras.0 + 1;
-        let column = source[self.lexer.extras.1..span.end].len() + 1;
-        let index = source[..span.end].len();
-
-        Span::new(line, column, index)
+    /// Decodes the current string literal.
+    ///
+    /// Invalid escape sequences are kept as they are written and reported as
+    /// warnings.
+    fn decode_string_literal(&mut self) -> Result<String, ParseError> {
+        let start = self.cur_token.as_ref().map_or(0, |token| token.span.start);
+        let (decoded, invalid) = unescape_string_literal_lossy(self.text())
+            .map_err(|_| self.error(ParseErrorKind::UnrecognizedToken))?;
+        self.warnings
+            .extend(invalid.into_iter().map(|e| ParseWarning::InvalidEscape {
+                span: SourceSpan::new((start + e.range.start).into(), e.range.len()),
+            }));
+        Ok(decoded)
     }
 
     fn error(&self, kind: ParseErrorKind) -> ParseError {
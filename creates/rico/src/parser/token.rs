@@ -1,9 +1,12 @@
+use miette::SourceSpan;
+
 use crate::lexer::Token;
-use crate::parser::error::ParseError;
+use crate::parser::error::{ParseError, ParseWarning};
+use crate::parser::factory::create_identifier;
 use crate::parser::Parser;
 
 use super::error::ParseErrorKind;
-use super::Comment;
+use super::{Comment, Common};
 
 impl<'a> Parser<'a> {
     pub(crate) fn with_error_boundary<T>(
@@ -20,15 +23,6 @@ impl<'a> Parser<'a> {
             Some(_) | None => Err(self.error(ParseErrorKind::UnexpectedToken)),
         }
     }
-    pub(crate) fn expect_token_with_error(
-        &mut self,
-        expected: Token,
-        error_kind: ParseErrorKind,
-    ) -> Result<(), ParseError> {
-        let result = self.expect_token(expected);
-        self.with_error_boundary(result, error_kind)
-    }
-
     pub(crate) fn consume(&mut self, token: Token) -> Result<(), ParseError> {
         self.advance();
         self.expect_token(token)
@@ -43,6 +37,36 @@ impl<'a> Parser<'a> {
         self.with_error_boundary(result, error_kind)
     }
 
+    /// Checks that the current token is a name, i.e. an identifier or a
+    /// keyword, and returns it as an identifier.
+    pub(crate) fn expect_name(&mut self, error_kind: ParseErrorKind) -> Result<Common, ParseError> {
+        match self.cur_token.as_ref() {
+            Some(token) if token.token == Token::Identifier => {}
+            Some(token) if token.token.is_keyword() => {
+                if self.strict {
+                    let span = token.span.clone();
+                    self.warnings.push(ParseWarning::KeywordAsName {
+                        keyword: token.text.to_string(),
+                        span: SourceSpan::new(span.start.into(), span.end - span.start),
+                    });
+                }
+            }
+            _ => return Err(self.error(error_kind)),
+        }
+        Ok(create_identifier(
+            self.get_token_loc(),
+            self.text().to_owned(),
+        ))
+    }
+
+    pub(crate) fn consume_name(
+        &mut self,
+        error_kind: ParseErrorKind,
+    ) -> Result<Common, ParseError> {
+        self.advance();
+        self.expect_name(error_kind)
+    }
+
     pub(crate) fn take_pending_comments(&mut self) -> Vec<Comment> {
         std::mem::take(&mut self.pending_comments)
     }
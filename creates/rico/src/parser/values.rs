@@ -83,11 +83,19 @@ impl<'a> Parser<'a> {
 
         match self.token() {
             Some(token) => match token {
-                Token::StringLiteral
-                | Token::IntegerLiteral
+                Token::StringLiteral => {
+                    let decoded = self.decode_string_literal()?;
+                    Ok(create_string_value(
+                        self.get_token_loc(),
+                        self.text(),
+                        decoded,
+                    ))
+                }
+                Token::IntegerLiteral
                 | Token::DoubleLiteral
                 | Token::BooleanLiteral
                 | Token::HexLiteral => {
+                    self.check_numeric_literal()?;
                     Ok(create_const_value(token, self.get_token_loc(), self.text()))
                 }
                 Token::Identifier => Ok(create_identifier_value(self.get_token_loc(), self.text())),
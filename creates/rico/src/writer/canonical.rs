@@ -0,0 +1,85 @@
+//! Canonicalization helpers for the writer.
+//!
+//! When [`WriterOptions::canonical`](super::WriterOptions::canonical) is enabled,
+//! semantically equal documents are written as byte-identical text:
+//! - Includes and namespaces are hoisted and sorted alphabetically
+//! - Struct, union and exception fields are ordered by field ID
+//! - String literals use double quotes
+//! - Enum values are written in decimal form
+//! - Redundant member separators are dropped
+
+use std::borrow::Cow;
+
+use crate::ast::string::{quote_string, unescape_string_literal};
+use crate::ast::*;
+
+/// Returns the document members in canonical order.
+///
+/// Includes come first, then namespaces, each sorted alphabetically. All other
+/// definitions keep their original order, since it may carry meaning for readers.
+pub(crate) fn sorted_members(members: &[DocumentMembers]) -> Vec<&DocumentMembers> {
+    let mut includes = Vec::new();
+    let mut namespaces = Vec::new();
+    let mut definitions = Vec::new();
+
+    for member in members {
+        match member {
+            DocumentMembers::Include(_) => includes.push(member),
+            DocumentMembers::Namespace(_) => namespaces.push(member),
+            _ => definitions.push(member),
+        }
+    }
+
+    includes.sort_by_key(|member| match member {
+        DocumentMembers::Include(inc) => normalize_string_literal(&inc.name.value).into_owned(),
+        _ => unreachable!(),
+    });
+    namespaces.sort_by(|a, b| match (a, b) {
+        (DocumentMembers::Namespace(a), DocumentMembers::Namespace(b)) => {
+            (&a.scope.value, &a.name.value).cmp(&(&b.scope.value, &b.name.value))
+        }
+        _ => unreachable!(),
+    });
+
+    includes
+        .into_iter()
+        .chain(namespaces)
+        .chain(definitions)
+        .collect()
+}
+
+/// Returns the fields ordered by field ID. Fields without an ID keep their
+/// relative order and are placed after all numbered fields.
+pub(crate) fn sorted_fields(fields: &[Field]) -> Vec<&Field> {
+    let mut fields: Vec<&Field> = fields.iter().collect();
+    fields.sort_by_key(|field| {
+        let id = field
+            .field_id
+            .as_ref()
+            .and_then(|id| id.value.parse::<i64>().ok());
+        (id.is_none(), id)
+    });
+    fields
+}
+
+/// Rewrites a string literal to use double quotes.
+///
+/// The literal is decoded and escaped again, so equal strings are written the
+/// same way, e.g. `'it\'s'` and `"it's"` or `"\u00e9"` and `"é"`. Literals with
+/// invalid escapes are kept as they are.
+pub(crate) fn normalize_string_literal(literal: &str) -> Cow<'_, str> {
+    match unescape_string_literal(literal) {
+        Ok(decoded) => Cow::Owned(quote_string(&decoded)),
+        Err(_) => Cow::Borrowed(literal),
+    }
+}
+
+/// Rewrites an integer literal in decimal form, e.g. `0x10` becomes `16`.
+///
+/// Literals that cannot be represented as an `i64` are returned unchanged.
+pub(crate) fn normalize_int_literal(literal: &str) -> Cow<'_, str> {
+    match parse_int_literal(literal) {
+        Some(value) => Cow::Owned(value.to_string()),
+        None => Cow::Borrowed(literal),
+    }
+}
@@ -3,86 +3,98 @@
 //! This module provides common writing operations that are used across different
 //! Thrift elements, such as writing comments, annotations, fields, and parameters.
 
-use super::Writer;
+use super::layout::Doc;
+use super::{Output, Writer};
 use crate::ast::*;
-use std::fmt::Write;
+use std::fmt::{self, Write};
 
 impl Writer {
     /// Writes comments to the output string with proper indentation.
     /// Handles both single-line and block comments.
-    pub(crate) fn write_comments(&mut self, output: &mut String, comments: &[Comment]) {
+    pub(crate) fn write_comments(
+        &mut self,
+        output: &mut Output<'_>,
+        comments: &[Comment],
+    ) -> fmt::Result {
         for comment in comments {
-            self.write_indent(output);
-            writeln!(output, "{}", comment.value).unwrap();
+            self.write_indent(output)?;
+            writeln!(output, "{}", comment.value)?;
         }
+        Ok(())
     }
 
     /// Writes annotations to the output string.
     /// Annotations are written in the format: (key = value, key2 = value2)
     pub(crate) fn write_annotations(
         &mut self,
-        output: &mut String,
+        output: &mut Output<'_>,
         annotations: &Option<Annotations>,
-    ) {
+    ) -> fmt::Result {
         if let Some(annotations) = annotations {
-            write!(output, " (").unwrap();
+            write!(output, " (")?;
             for (i, annotation) in annotations.members.iter().enumerate() {
                 if i > 0 {
-                    write!(output, ", ").unwrap();
+                    write!(output, ", ")?;
                 }
                 write!(
                     output,
                     "{} = {}",
-                    annotation.name.value, annotation.value.value
-                )
-                .unwrap();
+                    annotation.name.value,
+                    self.string_literal(&annotation.value.value)
+                )?;
             }
-            write!(output, ")").unwrap();
+            write!(output, ")")?;
         }
+        Ok(())
     }
 
     /// Writes a field definition to the output string.
     /// Handles field ID, required/optional modifier, type, name, default value, and annotations.
-    pub(crate) fn write_field(&mut self, output: &mut String, field: &Field) {
-        self.write_comments(output, &field.comments);
-        self.write_indent(output);
+    pub(crate) fn write_field(&mut self, output: &mut Output<'_>, field: &Field) -> fmt::Result {
+        self.write_comments(output, &field.comments)?;
+        self.write_indent(output)?;
 
         if let Some(id) = &field.field_id {
-            write!(output, "{}: ", id.value).unwrap();
+            write!(output, "{}: ", id.value)?;
         }
 
         match field.required_type.as_str() {
-            "required" => write!(output, "required ").unwrap(),
-            "optional" => write!(output, "optional ").unwrap(),
+            "required" => write!(output, "required ")?,
+            "optional" => write!(output, "optional ")?,
             _ => {}
         }
 
-        self.write_field_type(output, &field.field_type);
-        write!(output, " {}", field.name.value).unwrap();
+        self.write_field_type(output, &field.field_type)?;
+        write!(output, " {}", field.name.value)?;
 
         if let Some(default) = &field.default_value {
-            write!(output, " = ").unwrap();
-            self.write_field_value(output, default);
+            write!(output, " = ")?;
+            self.write_field_value(output, default)?;
         }
 
-        self.write_annotations(output, &field.annotations);
-        writeln!(output, ",").unwrap();
+        self.write_annotations(output, &field.annotations)?;
+        writeln!(output, "{}", self.member_separator())?;
+        Ok(())
     }
 
-    /// Writes a parameter definition to the output string.
+    /// Builds the layout document for a parameter definition.
     /// Similar to write_field but with specific formatting for function parameters.
-    pub(crate) fn write_param(&mut self, output: &mut String, field: &Field, is_first: bool) {
-        if !is_first {
-            write!(output, ", ").unwrap();
-        }
+    pub(crate) fn param_doc(&mut self, field: &Field) -> Result<Doc, fmt::Error> {
+        let mut head = String::new();
+        let mut output = Output::new(&mut head);
         if let Some(id) = &field.field_id {
-            write!(output, "{}: ", id.value).unwrap();
-        }
-        self.write_field_type(output, &field.field_type);
-        write!(output, " {}", field.name.value).unwrap();
-        if let Some(default) = &field.default_value {
-            write!(output, " = ").unwrap();
-            self.write_field_value(output, default);
+            write!(output, "{}: ", id.value)?;
         }
+        self.write_field_type(&mut output, &field.field_type)?;
+        write!(output, " {}", field.name.value)?;
+
+        Ok(match &field.default_value {
+            Some(default) => Doc::Concat(vec![
+                Doc::text(head),
+                Doc::text(" = "),
+                self.field_value_doc(default),
+            ]),
+            None => Doc::text(head),
+        })
     }
 }
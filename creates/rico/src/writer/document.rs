@@ -11,168 +11,206 @@
 //! - Exceptions
 //! - Services and their functions
 
-use super::Writer;
+use super::canonical;
+use super::layout::Doc;
+use super::{Output, Writer};
 use crate::ast::*;
-use std::fmt::Write;
+use std::fmt::{self, Write};
 
 impl Writer {
     /// Writes a namespace definition to the output string.
-    pub(crate) fn write_namespace(&mut self, output: &mut String, ns: &Namespace) {
-        self.write_comments(output, &ns.comments);
-        writeln!(output, "namespace {} {}", ns.scope.value, ns.name.value).unwrap();
+    pub(crate) fn write_namespace(
+        &mut self,
+        output: &mut Output<'_>,
+        ns: &Namespace,
+    ) -> fmt::Result {
+        self.write_comments(output, &ns.comments)?;
+        writeln!(output, "namespace {} {}", ns.scope.value, ns.name.value)?;
+        Ok(())
     }
 
     /// Writes an include statement to the output string.
-    pub(crate) fn write_include(&mut self, output: &mut String, inc: &Include) {
-        self.write_comments(output, &inc.comments);
-        writeln!(output, "include {}", inc.name.value).unwrap();
+    pub(crate) fn write_include(&mut self, output: &mut Output<'_>, inc: &Include) -> fmt::Result {
+        self.write_comments(output, &inc.comments)?;
+        writeln!(output, "include {}", self.string_literal(&inc.name.value))?;
+        Ok(())
     }
 
     /// Writes a constant definition to the output string.
-    pub(crate) fn write_const(&mut self, output: &mut String, c: &Const) {
-        self.write_comments(output, &c.comments);
-        write!(output, "const ").unwrap();
-        self.write_field_type(output, &c.field_type);
-        write!(output, " {} = ", c.name.value).unwrap();
-        self.write_field_value(output, &c.value);
-        writeln!(output).unwrap();
+    pub(crate) fn write_const(&mut self, output: &mut Output<'_>, c: &Const) -> fmt::Result {
+        self.write_comments(output, &c.comments)?;
+        write!(output, "const ")?;
+        self.write_field_type(output, &c.field_type)?;
+        write!(output, " {} = ", c.name.value)?;
+        self.write_field_value(output, &c.value)?;
+        writeln!(output)?;
+        Ok(())
     }
 
     /// Writes a typedef definition to the output string.
-    pub(crate) fn write_typedef(&mut self, output: &mut String, td: &Typedef) {
-        self.write_comments(output, &td.comments);
-        write!(output, "typedef ").unwrap();
-        self.write_field_type(output, &td.field_type);
-        writeln!(output, " {}", td.name.value).unwrap();
+    pub(crate) fn write_typedef(&mut self, output: &mut Output<'_>, td: &Typedef) -> fmt::Result {
+        self.write_comments(output, &td.comments)?;
+        write!(output, "typedef ")?;
+        self.write_field_type(output, &td.field_type)?;
+        writeln!(output, " {}", td.name.value)?;
+        Ok(())
     }
 
     /// Writes an enum definition to the output string.
     /// Handles enum members, their values, and annotations.
-    pub(crate) fn write_enum(&mut self, output: &mut String, e: &Enum) {
-        self.write_comments(output, &e.comments);
-        writeln!(output, "enum {} {{", e.name.value).unwrap();
+    pub(crate) fn write_enum(&mut self, output: &mut Output<'_>, e: &Enum) -> fmt::Result {
+        self.write_comments(output, &e.comments)?;
+        writeln!(output, "enum {} {{", e.name.value)?;
         self.indent();
 
         for member in &e.members {
-            self.write_comments(output, &member.comments);
-            self.write_indent(output);
-            write!(output, "{}", member.name.value).unwrap();
-            if let Some(value) = &member.initializer {
-                write!(output, " = {}", value.value.value).unwrap();
-            }
-            self.write_annotations(output, &member.annotations);
-            writeln!(output, ",").unwrap();
+            self.write_enum_member(output, member)?;
         }
 
         self.dedent();
-        write!(output, "}}").unwrap();
-        self.write_annotations(output, &e.annotations);
-        writeln!(output).unwrap();
+        write!(output, "}}")?;
+        self.write_annotations(output, &e.annotations)?;
+        writeln!(output)?;
+        Ok(())
+    }
+
+    /// Writes an enum member to the output string.
+    /// In canonical mode the member value is written in decimal form.
+    pub(crate) fn write_enum_member(
+        &mut self,
+        output: &mut Output<'_>,
+        member: &EnumMember,
+    ) -> fmt::Result {
+        self.write_comments(output, &member.comments)?;
+        self.write_indent(output)?;
+        write!(output, "{}", member.name.value)?;
+        if let Some(value) = &member.initializer {
+            if self.options.canonical {
+                let value = canonical::normalize_int_literal(&value.value.value);
+                write!(output, " = {}", value)?;
+            } else {
+                write!(output, " = {}", value.value.value)?;
+            }
+        }
+        self.write_annotations(output, &member.annotations)?;
+        writeln!(output, "{}", self.member_separator())
     }
 
     /// Writes a struct definition to the output string.
     /// Handles struct fields, their types, and annotations.
-    pub(crate) fn write_struct(&mut self, output: &mut String, s: &Struct) {
-        self.write_comments(output, &s.comments);
-        writeln!(output, "struct {} {{", s.name.value).unwrap();
+    /// In canonical mode fields are ordered by field ID.
+    pub(crate) fn write_struct(&mut self, output: &mut Output<'_>, s: &Struct) -> fmt::Result {
+        self.write_comments(output, &s.comments)?;
+        writeln!(output, "struct {} {{", s.name.value)?;
         self.indent();
 
-        for member in &s.members {
-            self.write_field(output, member);
+        for member in self.struct_fields(&s.members) {
+            self.write_field(output, member)?;
         }
 
         self.dedent();
-        write!(output, "}}").unwrap();
-        self.write_annotations(output, &s.annotations);
-        writeln!(output).unwrap();
+        write!(output, "}}")?;
+        self.write_annotations(output, &s.annotations)?;
+        writeln!(output)?;
+        Ok(())
     }
 
     /// Writes a union definition to the output string.
     /// Handles union fields and their annotations.
-    pub(crate) fn write_union(&mut self, output: &mut String, u: &Union) {
-        self.write_comments(output, &u.comments);
-        writeln!(output, "union {} {{", u.name.value).unwrap();
+    pub(crate) fn write_union(&mut self, output: &mut Output<'_>, u: &Union) -> fmt::Result {
+        self.write_comments(output, &u.comments)?;
+        writeln!(output, "union {} {{", u.name.value)?;
         self.indent();
 
-        for member in &u.members {
-            self.write_field(output, member);
+        for member in self.struct_fields(&u.members) {
+            self.write_field(output, member)?;
         }
 
         self.dedent();
-        write!(output, "}}").unwrap();
-        self.write_annotations(output, &u.annotations);
-        writeln!(output).unwrap();
+        write!(output, "}}")?;
+        self.write_annotations(output, &u.annotations)?;
+        writeln!(output)?;
+        Ok(())
     }
 
     /// Writes an exception definition to the output string.
     /// Handles exception fields and their annotations.
-    pub(crate) fn write_exception(&mut self, output: &mut String, e: &Exception) {
-        self.write_comments(output, &e.comments);
-        writeln!(output, "exception {} {{", e.name.value).unwrap();
+    pub(crate) fn write_exception(
+        &mut self,
+        output: &mut Output<'_>,
+        e: &Exception,
+    ) -> fmt::Result {
+        self.write_comments(output, &e.comments)?;
+        writeln!(output, "exception {} {{", e.name.value)?;
         self.indent();
 
-        for member in &e.members {
-            self.write_field(output, member);
+        for member in self.struct_fields(&e.members) {
+            self.write_field(output, member)?;
         }
 
         self.dedent();
-        write!(output, "}}").unwrap();
-        self.write_annotations(output, &e.annotations);
-        writeln!(output).unwrap();
+        write!(output, "}}")?;
+        self.write_annotations(output, &e.annotations)?;
+        writeln!(output)?;
+        Ok(())
     }
 
     /// Writes a service definition to the output string.
     /// Handles service functions, inheritance, and annotations.
-    pub(crate) fn write_service(&mut self, output: &mut String, s: &Service) {
-        self.write_comments(output, &s.comments);
-        write!(output, "service {}", s.name.value).unwrap();
+    pub(crate) fn write_service(&mut self, output: &mut Output<'_>, s: &Service) -> fmt::Result {
+        self.write_comments(output, &s.comments)?;
+        write!(output, "service {}", s.name.value)?;
         if let Some(extends) = &s.extends {
-            write!(output, " extends {}", extends.value).unwrap();
+            write!(output, " extends {}", extends.value)?;
         }
-        writeln!(output, " {{").unwrap();
+        writeln!(output, " {{")?;
         self.indent();
 
         for member in &s.members {
-            self.write_function(output, member);
+            self.write_function(output, member)?;
         }
 
         self.dedent();
-        write!(output, "}}").unwrap();
-        self.write_annotations(output, &s.annotations);
-        writeln!(output).unwrap();
+        write!(output, "}}")?;
+        self.write_annotations(output, &s.annotations)?;
+        writeln!(output)?;
+        Ok(())
     }
 
     /// Writes a function definition to the output string.
     /// Handles function parameters, return type, exceptions, and annotations.
-    pub(crate) fn write_function(&mut self, output: &mut String, f: &Function) {
-        self.write_comments(output, &f.comments);
-        self.write_indent(output);
+    /// Parameter lists that do not fit in `max_width` are broken one per line.
+    pub(crate) fn write_function(&mut self, output: &mut Output<'_>, f: &Function) -> fmt::Result {
+        self.write_comments(output, &f.comments)?;
+        self.write_indent(output)?;
 
         if f.oneway {
-            write!(output, "oneway ").unwrap();
+            write!(output, "oneway ")?;
         }
 
-        self.write_field_type(output, &f.return_type);
-        write!(output, " {}(", f.name.value).unwrap();
+        self.write_field_type(output, &f.return_type)?;
+        write!(output, " {}", f.name.value)?;
 
-        self.indent();
-        for (i, param) in f.params.iter().enumerate() {
-            self.write_param(output, param, i == 0);
-        }
-        self.dedent();
-        write!(output, ")").unwrap();
+        let params = f
+            .params
+            .iter()
+            .map(|param| self.param_doc(param))
+            .collect::<Result<_, _>>()?;
+        let mut signature = vec![self.bracketed("(", params, ")")];
 
         if let Some(throws) = &f.throws {
-            write!(output, " throws (").unwrap();
-            self.indent();
-            for (i, throw) in throws.iter().enumerate() {
-                self.write_param(output, throw, i == 0);
-            }
-            self.dedent();
-            write!(output, ")").unwrap();
+            let throws = throws
+                .iter()
+                .map(|throw| self.param_doc(throw))
+                .collect::<Result<_, _>>()?;
+            signature.push(Doc::text(" throws "));
+            signature.push(self.bracketed("(", throws, ")"));
         }
 
-        self.write_annotations(output, &f.annotations);
-        writeln!(output).unwrap();
+        self.write_doc(output, &Doc::Concat(signature))?;
+
+        self.write_annotations(output, &f.annotations)?;
+        writeln!(output)?;
+        Ok(())
     }
 }
@@ -0,0 +1,78 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// Errors reported by [`Writer::try_write`](super::Writer::try_write) when a
+/// document violates the invariants the writer relies on.
+///
+/// Every variant carries the JSON path of the offending node, using the field
+/// names of the serialized AST (for example `$.members[1].members[0].fieldID`).
+#[derive(Error, Debug, Diagnostic, Clone, PartialEq, Eq)]
+pub enum WriterError {
+    #[error("Empty identifier at {path}")]
+    #[diagnostic(
+        code(rico::writer::empty_identifier),
+        help("Names of definitions, fields, members and annotations must not be empty")
+    )]
+    EmptyIdentifier { path: String },
+
+    #[error("Invalid identifier \"{value}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::invalid_identifier),
+        help("Identifiers must start with a letter or underscore and contain only letters, digits, underscores and dots")
+    )]
+    InvalidIdentifier { path: String, value: String },
+
+    #[error("Invalid type \"{value}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::invalid_type),
+        help("Types must be a base type, a container type or an identifier, not a literal")
+    )]
+    InvalidType { path: String, value: String },
+
+    #[error("Invalid field ID \"{value}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::invalid_field_id),
+        help("Field IDs must be positive integers, use like: \"1\"")
+    )]
+    InvalidFieldId { path: String, value: String },
+
+    #[error("Invalid value \"{value}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::invalid_value),
+        help("The value does not match its literal kind")
+    )]
+    InvalidValue { path: String, value: String },
+
+    #[error("String literal {value} does not match its decoded text \"{decoded}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::mismatched_string),
+        help("`value` is the text that is written; update `decoded` along with it, or remove `decoded`")
+    )]
+    MismatchedString {
+        path: String,
+        value: String,
+        decoded: String,
+    },
+
+    #[error("Invalid required type \"{value}\" at {path}")]
+    #[diagnostic(
+        code(rico::writer::invalid_required_type),
+        help("The required type must be one of \"required\", \"optional\" or \"default\"")
+    )]
+    InvalidRequiredType { path: String, value: String },
+}
+
+impl WriterError {
+    /// Returns the JSON path of the node that caused the error.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::EmptyIdentifier { path }
+            | Self::InvalidIdentifier { path, .. }
+            | Self::InvalidType { path, .. }
+            | Self::InvalidFieldId { path, .. }
+            | Self::InvalidValue { path, .. }
+            | Self::MismatchedString { path, .. }
+            | Self::InvalidRequiredType { path, .. } => path,
+        }
+    }
+}
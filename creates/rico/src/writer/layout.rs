@@ -0,0 +1,194 @@
+//! Line-width aware layout for Thrift IDL output.
+//!
+//! This module implements a small Wadler-style pretty printer. Writers describe
+//! their output as a [`Doc`] tree made of text, line breaks, nesting and groups,
+//! and the renderer decides for every group whether it fits on the current line
+//! (flat mode) or has to be broken one element per line (break mode).
+
+use std::fmt;
+
+use super::Indent;
+
+/// A document describing a piece of output and its possible line breaks.
+#[derive(Debug, Clone)]
+pub(crate) enum Doc {
+    /// Literal text that never contains a newline
+    Text(String),
+    /// A space in flat mode, a newline plus indentation in break mode
+    Line,
+    /// Nothing in flat mode, a newline plus indentation in break mode
+    SoftLine,
+    /// Text that is only emitted when the enclosing group is broken
+    IfBreak(String),
+    /// Increases the indentation of the inner document by the given number of columns
+    Nest(usize, Box<Doc>),
+    /// A unit that is laid out flat if it fits, and broken otherwise
+    Group(Box<Doc>),
+    /// A sequence of documents
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub(crate) fn text(text: impl Into<String>) -> Self {
+        Doc::Text(text.into())
+    }
+
+    pub(crate) fn nest(indent: usize, doc: Doc) -> Self {
+        Doc::Nest(indent, Box::new(doc))
+    }
+
+    pub(crate) fn group(doc: Doc) -> Self {
+        Doc::Group(Box::new(doc))
+    }
+
+    /// Builds a bracketed, comma separated container such as `[a, b]` or `f(a, b)`.
+    ///
+    /// When the group is broken every element goes on its own line, nested by
+    /// `indent` columns, optionally followed by a trailing comma.
+    pub(crate) fn bracketed(
+        open: &str,
+        elements: Vec<Doc>,
+        close: &str,
+        indent: usize,
+        trailing_comma: bool,
+    ) -> Self {
+        if elements.is_empty() {
+            return Doc::text(format!("{}{}", open, close));
+        }
+
+        let mut inner = vec![Doc::SoftLine];
+        for (i, element) in elements.into_iter().enumerate() {
+            if i > 0 {
+                inner.push(Doc::text(","));
+                inner.push(Doc::Line);
+            }
+            inner.push(element);
+        }
+        if trailing_comma {
+            inner.push(Doc::IfBreak(",".to_string()));
+        }
+
+        Doc::group(Doc::Concat(vec![
+            Doc::text(open),
+            Doc::nest(indent, Doc::Concat(inner)),
+            Doc::SoftLine,
+            Doc::text(close),
+        ]))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+/// Renders documents into text for a given maximum line width.
+pub(crate) struct Renderer {
+    /// Maximum line width, `None` means unlimited
+    max_width: Option<usize>,
+    /// How indentation columns are written
+    indent: Indent,
+}
+
+impl Renderer {
+    pub(crate) fn new(max_width: Option<usize>, indent: Indent) -> Self {
+        Self { max_width, indent }
+    }
+
+    /// Renders `doc` into `output`, assuming the output currently sits at `column`
+    /// and that broken lines are indented by `indent` columns.
+    pub(crate) fn render<W: fmt::Write>(
+        &self,
+        doc: &Doc,
+        indent: usize,
+        column: usize,
+        output: &mut W,
+    ) -> fmt::Result {
+        let mut column = column;
+        let mut stack: Vec<(usize, Mode, &Doc)> = vec![(indent, Mode::Break, doc)];
+
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    output.write_str(text)?;
+                    column += text.chars().count();
+                }
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => {
+                    output.write_char('\n')?;
+                    self.indent.write(output, indent)?;
+                    column = indent;
+                }
+                Doc::Line => {
+                    output.write_char(' ')?;
+                    column += 1;
+                }
+                Doc::SoftLine => {}
+                Doc::IfBreak(text) => {
+                    if mode == Mode::Break {
+                        output.write_str(text)?;
+                        column += text.chars().count();
+                    }
+                }
+                Doc::Nest(extra, inner) => stack.push((indent + extra, mode, inner)),
+                Doc::Group(inner) => {
+                    let mode = if mode == Mode::Flat || self.fits(inner, column, &stack) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    stack.push((indent, mode, inner));
+                }
+                Doc::Concat(docs) => {
+                    for doc in docs.iter().rev() {
+                        stack.push((indent, mode, doc));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether `doc` laid out flat, followed by the rest of the pending
+    /// output up to its next possible line break, fits in the remaining width.
+    fn fits(&self, doc: &Doc, column: usize, rest: &[(usize, Mode, &Doc)]) -> bool {
+        let Some(max_width) = self.max_width else {
+            return true;
+        };
+        let mut remaining = max_width as isize - column as isize;
+        let mut rest = rest.iter().rev();
+        let mut pending: Vec<(Mode, &Doc)> = vec![(Mode::Flat, doc)];
+
+        loop {
+            if remaining < 0 {
+                return false;
+            }
+            let (mode, doc) = match pending.pop() {
+                Some(item) => item,
+                None => match rest.next() {
+                    Some((_, mode, doc)) => (*mode, *doc),
+                    None => return true,
+                },
+            };
+            match doc {
+                Doc::Text(text) => remaining -= text.chars().count() as isize,
+                Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+                Doc::Line => remaining -= 1,
+                Doc::SoftLine => {}
+                Doc::IfBreak(text) => {
+                    if mode == Mode::Break {
+                        remaining -= text.chars().count() as isize;
+                    }
+                }
+                Doc::Nest(_, inner) => pending.push((mode, inner)),
+                Doc::Group(inner) => pending.push((mode, inner)),
+                Doc::Concat(docs) => {
+                    for doc in docs.iter().rev() {
+                        pending.push((mode, doc));
+                    }
+                }
+            }
+        }
+    }
+}
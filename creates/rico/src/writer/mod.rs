@@ -13,14 +13,129 @@
 //! - Services
 //!
 //! The writer maintains proper indentation and formatting according to common Thrift IDL conventions.
+//! Output can be collected into a `String` or streamed into any `io::Write` or
+//! `fmt::Write` sink, and single nodes can be written on their own through the
+//! [`Writable`] trait.
 
+mod canonical;
 mod common;
 mod document;
+mod error;
+mod layout;
+mod node;
+mod output;
 mod types;
+mod validate;
 mod values;
 
+pub use error::WriterError;
+pub use node::Writable;
+
+use crate::ast::string::{is_quoted, quote_string};
 use crate::ast::*;
-use std::fmt::Write;
+use layout::{Doc, Renderer};
+use output::{IoAdapter, Output};
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use validate::Validator;
+
+/// Number of columns a tab counts for when measuring line width.
+const TAB_WIDTH: usize = 4;
+
+/// Indentation of nested definitions and broken containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// The given number of spaces per level
+    Spaces(usize),
+    /// One tab per level, counted as four columns for the line width
+    Tabs,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(2)
+    }
+}
+
+impl Indent {
+    /// Returns the number of columns of one indentation level.
+    fn width(self) -> usize {
+        match self {
+            Indent::Spaces(width) => width,
+            Indent::Tabs => TAB_WIDTH,
+        }
+    }
+
+    /// Writes the indentation for the given number of columns.
+    fn write(self, output: &mut dyn fmt::Write, columns: usize) -> fmt::Result {
+        match self {
+            Indent::Spaces(_) => write!(output, "{:width$}", "", width = columns),
+            Indent::Tabs => {
+                for _ in 0..columns / TAB_WIDTH {
+                    output.write_char('\t')?;
+                }
+                write!(output, "{:width$}", "", width = columns % TAB_WIDTH)
+            }
+        }
+    }
+}
+
+/// Separator written after struct fields and enum members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Separator {
+    /// `1: i32 id,`
+    #[default]
+    Comma,
+    /// `1: i32 id;`
+    Semicolon,
+    /// `1: i32 id`
+    None,
+}
+
+impl Separator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Separator::Comma => ",",
+            Separator::Semicolon => ";",
+            Separator::None => "",
+        }
+    }
+}
+
+/// Formatting options for the [`Writer`].
+///
+/// # Example
+///
+/// ```rust
+/// use rico::writer::{Writer, WriterOptions};
+///
+/// let writer = Writer::with_options(WriterOptions {
+///     max_width: Some(80),
+///     trailing_comma: true,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriterOptions {
+    /// Maximum line width. Constant lists and maps, parameter lists and throws
+    /// clauses that do not fit are broken one element per line.
+    /// `None` keeps everything on a single line.
+    pub max_width: Option<usize>,
+    /// Whether to emit a trailing comma after the last element of a broken
+    /// container or parameter list.
+    pub trailing_comma: bool,
+    /// Enables canonical output: includes and namespaces are sorted, struct
+    /// fields are ordered by field ID, string literals use double quotes, enum
+    /// values are written in decimal and redundant separators are dropped.
+    /// Semantically equal documents then produce byte-identical text.
+    pub canonical: bool,
+    /// Indentation per nesting level, two spaces by default.
+    pub indent: Indent,
+    /// Separator after struct fields and enum members, a comma by default.
+    /// Canonical output always drops it.
+    pub separator: Separator,
+}
 
 /// A writer that converts Thrift AST nodes back to Thrift IDL text format.
 ///
@@ -40,22 +155,38 @@ use std::fmt::Write;
 /// let thrift_text = writer.write(&document);
 /// ```
 pub struct Writer {
-    /// Current indentation level (each level represents 2 spaces)
+    /// Current indentation level
     indent_level: usize,
+    /// Formatting options
+    options: WriterOptions,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Writer {
     /// Creates a new Writer instance with default settings.
     pub fn new() -> Self {
-        Self { indent_level: 0 }
+        Self::with_options(WriterOptions::default())
+    }
+
+    /// Creates a new Writer instance with the given formatting options.
+    pub fn with_options(options: WriterOptions) -> Self {
+        Self {
+            indent_level: 0,
+            options,
+        }
     }
 
-    /// Increases the current indentation level by 1 (2 spaces).
+    /// Increases the current indentation level by 1.
     fn indent(&mut self) {
         self.indent_level += 1;
     }
 
-    /// Decreases the current indentation level by 1 (2 spaces).
+    /// Decreases the current indentation level by 1.
     /// Will not decrease below 0.
     fn dedent(&mut self) {
         if self.indent_level > 0 {
@@ -63,10 +194,65 @@ impl Writer {
         }
     }
 
-    /// Writes the current indentation to the output string.
-    /// Each indentation level represents 2 spaces.
-    fn write_indent(&mut self, output: &mut String) {
-        write!(output, "{:width$}", "", width = self.indent_level * 2).unwrap();
+    /// Writes the current indentation to the output.
+    fn write_indent(&mut self, output: &mut Output<'_>) -> fmt::Result {
+        self.options.indent.write(output, self.indent_columns())
+    }
+
+    /// Returns the width of the current indentation in columns.
+    fn indent_columns(&self) -> usize {
+        self.indent_level * self.options.indent.width()
+    }
+
+    /// Builds a bracketed container document using the configured options.
+    fn bracketed(&self, open: &str, elements: Vec<Doc>, close: &str) -> Doc {
+        Doc::bracketed(
+            open,
+            elements,
+            close,
+            self.options.indent.width(),
+            self.options.trailing_comma,
+        )
+    }
+
+    /// Returns the separator written after fields and enum members.
+    fn member_separator(&self) -> &'static str {
+        if self.options.canonical {
+            ""
+        } else {
+            self.options.separator.as_str()
+        }
+    }
+
+    /// Returns a string literal, normalized to double quotes in canonical mode.
+    ///
+    /// Values without surrounding quotes, e.g. from builders or transforms,
+    /// are taken as decoded text and quoted and escaped. `value` is always the
+    /// text that is written; `decoded` is only checked by [`Writer::validate`].
+    fn string_literal<'s>(&self, literal: &'s str) -> Cow<'s, str> {
+        if !is_quoted(literal) {
+            Cow::Owned(quote_string(literal))
+        } else if self.options.canonical {
+            canonical::normalize_string_literal(literal)
+        } else {
+            Cow::Borrowed(literal)
+        }
+    }
+
+    /// Returns the fields of a struct-like definition, ordered by field ID in canonical mode.
+    fn struct_fields<'f>(&self, fields: &'f [Field]) -> Vec<&'f Field> {
+        if self.options.canonical {
+            canonical::sorted_fields(fields)
+        } else {
+            fields.iter().collect()
+        }
+    }
+
+    /// Lays out a document starting at the current column of the output.
+    fn write_doc(&mut self, output: &mut Output<'_>, doc: &Doc) -> fmt::Result {
+        let renderer = Renderer::new(self.options.max_width, self.options.indent);
+        let column = output.column();
+        renderer.render(doc, self.indent_columns(), column, output)
     }
 
     /// Writes a complete Thrift document to string format.
@@ -92,23 +278,124 @@ impl Writer {
     ///
     /// A string containing the formatted Thrift IDL text
     pub fn write(&mut self, doc: &Document) -> String {
-        let mut output = String::new();
+        self.write_node(doc)
+    }
 
-        for member in &doc.members {
-            match member {
-                DocumentMembers::Namespace(ns) => self.write_namespace(&mut output, ns),
-                DocumentMembers::Include(inc) => self.write_include(&mut output, inc),
-                DocumentMembers::Const(c) => self.write_const(&mut output, c),
-                DocumentMembers::Typedef(td) => self.write_typedef(&mut output, td),
-                DocumentMembers::Enum(e) => self.write_enum(&mut output, e),
-                DocumentMembers::Struct(s) => self.write_struct(&mut output, s),
-                DocumentMembers::Union(u) => self.write_union(&mut output, u),
-                DocumentMembers::Exception(e) => self.write_exception(&mut output, e),
-                DocumentMembers::Service(s) => self.write_service(&mut output, s),
-            }
-            writeln!(output).unwrap();
-        }
+    /// Validates a document and writes it to string format.
+    ///
+    /// Unlike [`Writer::write`], which accepts any document, this method first
+    /// checks the invariants the writer relies on, so documents built by hand or
+    /// deserialized from JSON do not produce invalid Thrift IDL.
+    ///
+    /// # Errors
+    ///
+    /// Returns a WriterError with the JSON path of the first offending node, e.g.
+    /// for empty identifiers, literals used as types, malformed string literals,
+    /// string literals whose `decoded` text does not match their `value`, or
+    /// non-numeric field IDs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::{Parser, Writer};
+    ///
+    /// let mut document = Parser::new("struct User { 1: string name }").parse().unwrap();
+    /// if let rico::DocumentMembers::Struct(s) = &mut document.members[0] {
+    ///     s.members[0].name.value.clear();
+    /// }
+    ///
+    /// let error = Writer::new().try_write(&document).unwrap_err();
+    /// assert_eq!(error.path(), "$.members[0].members[0].name");
+    /// ```
+    pub fn try_write(&mut self, doc: &Document) -> Result<String, WriterError> {
+        Self::validate(doc)?;
+        Ok(self.write(doc))
+    }
 
+    /// Checks that a document upholds the invariants required for writing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a WriterError with the JSON path of the first offending node.
+    pub fn validate(doc: &Document) -> Result<(), WriterError> {
+        Validator::new().validate_document(doc)
+    }
+
+    /// Streams a complete Thrift document into an [`io::Write`] sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first I/O error reported by the sink.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::{Parser, Writer};
+    ///
+    /// let document = Parser::new("typedef i64 UserId").parse().unwrap();
+    /// let mut buffer = Vec::new();
+    /// Writer::new().write_to(&document, &mut buffer).unwrap();
+    /// assert_eq!(buffer, b"typedef i64 UserId\n\n");
+    /// ```
+    pub fn write_to<W: io::Write>(&mut self, doc: &Document, output: W) -> io::Result<()> {
+        self.write_node_to(doc, output)
+    }
+
+    /// Streams a complete Thrift document into a [`fmt::Write`] sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sink fails to accept the output.
+    pub fn write_to_fmt<W: fmt::Write>(&mut self, doc: &Document, output: &mut W) -> fmt::Result {
+        self.write_node_to_fmt(doc, output)
+    }
+
+    /// Writes a single AST node, such as one `Struct`, `Service` or `FieldType`,
+    /// to string format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rico::{DocumentMembers, Parser, Writer};
+    ///
+    /// let document = Parser::new("struct User { 1: string name }").parse().unwrap();
+    /// if let DocumentMembers::Struct(s) = &document.members[0] {
+    ///     assert_eq!(Writer::new().write_node(&s.members[0].field_type), "string");
+    /// }
+    /// ```
+    pub fn write_node<N: Writable + ?Sized>(&mut self, node: &N) -> String {
+        let mut output = String::new();
+        self.write_node_to_fmt(node, &mut output)
+            .expect("writing to a String cannot fail");
         output
     }
+
+    /// Streams a single AST node into an [`io::Write`] sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first I/O error reported by the sink.
+    pub fn write_node_to<N: Writable + ?Sized, W: io::Write>(
+        &mut self,
+        node: &N,
+        output: W,
+    ) -> io::Result<()> {
+        let mut adapter = IoAdapter::new(output);
+        let result = self.write_node_to_fmt(node, &mut adapter);
+        adapter.finish(result)
+    }
+
+    /// Streams a single AST node into a [`fmt::Write`] sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sink fails to accept the output.
+    pub fn write_node_to_fmt<N: Writable + ?Sized, W: fmt::Write>(
+        &mut self,
+        node: &N,
+        output: &mut W,
+    ) -> fmt::Result {
+        self.indent_level = 0;
+        node.write_with(self, output)
+    }
 }
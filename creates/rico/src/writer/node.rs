@@ -0,0 +1,94 @@
+//! Writing of individual AST nodes.
+//!
+//! This module defines the [`Writable`] trait, which lets the [`Writer`] write
+//! any single node, such as one `Struct`, `Service` or `FieldType`, in addition
+//! to whole documents.
+
+use super::{Output, Writer};
+use crate::ast::*;
+use std::fmt::{self, Write};
+
+/// An AST node that can be written as Thrift IDL text by a [`Writer`].
+///
+/// Use [`Writer::write_node`], [`Writer::write_node_to`] or
+/// [`Writer::write_node_to_fmt`] to write a node.
+pub trait Writable {
+    /// Writes the node to the output using the given writer.
+    fn write_with(&self, writer: &mut Writer, output: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+/// Implements [`Writable`] by delegating to one of the writer's helpers.
+macro_rules! impl_writable {
+    ($($node:ty => $method:ident),* $(,)?) => {
+        $(
+            impl Writable for $node {
+                fn write_with(
+                    &self,
+                    writer: &mut Writer,
+                    output: &mut dyn fmt::Write,
+                ) -> fmt::Result {
+                    writer.$method(&mut Output::new(output), self)
+                }
+            }
+        )*
+    };
+}
+
+impl_writable! {
+    Document => write_document,
+    DocumentMembers => write_member,
+    Namespace => write_namespace,
+    Include => write_include,
+    Const => write_const,
+    Typedef => write_typedef,
+    Enum => write_enum,
+    EnumMember => write_enum_member,
+    Struct => write_struct,
+    Union => write_union,
+    Exception => write_exception,
+    Service => write_service,
+    Function => write_function,
+    Field => write_field,
+    FieldType => write_field_type,
+    FieldInitialValue => write_field_value,
+}
+
+impl Writer {
+    /// Writes a complete document, separating members by blank lines.
+    pub(crate) fn write_document(
+        &mut self,
+        output: &mut Output<'_>,
+        doc: &Document,
+    ) -> fmt::Result {
+        let members = if self.options.canonical {
+            super::canonical::sorted_members(&doc.members)
+        } else {
+            doc.members.iter().collect()
+        };
+
+        for member in members {
+            self.write_member(output, member)?;
+            writeln!(output)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single top-level definition.
+    pub(crate) fn write_member(
+        &mut self,
+        output: &mut Output<'_>,
+        member: &DocumentMembers,
+    ) -> fmt::Result {
+        match member {
+            DocumentMembers::Namespace(ns) => self.write_namespace(output, ns),
+            DocumentMembers::Include(inc) => self.write_include(output, inc),
+            DocumentMembers::Const(c) => self.write_const(output, c),
+            DocumentMembers::Typedef(td) => self.write_typedef(output, td),
+            DocumentMembers::Enum(e) => self.write_enum(output, e),
+            DocumentMembers::Struct(s) => self.write_struct(output, s),
+            DocumentMembers::Union(u) => self.write_union(output, u),
+            DocumentMembers::Exception(e) => self.write_exception(output, e),
+            DocumentMembers::Service(s) => self.write_service(output, s),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Output sinks for the writer.
+//!
+//! The writer emits text through [`Output`], which wraps any [`fmt::Write`]
+//! sink and keeps track of the current column for the layout engine.
+//! [`IoAdapter`] bridges [`io::Write`] sinks and keeps the underlying I/O error.
+
+use std::fmt;
+use std::io;
+
+use super::TAB_WIDTH;
+
+/// A [`fmt::Write`] sink that tracks the column of the next character.
+/// Tabs count as [`TAB_WIDTH`] columns.
+pub(crate) struct Output<'w> {
+    inner: &'w mut dyn fmt::Write,
+    column: usize,
+}
+
+impl<'w> Output<'w> {
+    pub(crate) fn new(inner: &'w mut dyn fmt::Write) -> Self {
+        Self { inner, column: 0 }
+    }
+
+    /// Returns the column, in characters, at which the next write starts.
+    pub(crate) fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Write for Output<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.rfind('\n') {
+            Some(index) => self.column = width(&s[index + 1..]),
+            None => self.column += width(s),
+        }
+        self.inner.write_str(s)
+    }
+}
+
+/// Returns the width of a line fragment in columns.
+fn width(s: &str) -> usize {
+    s.chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Adapts an [`io::Write`] sink to [`fmt::Write`], remembering the I/O error
+/// that caused a write to fail.
+pub(crate) struct IoAdapter<W> {
+    inner: W,
+    pub(crate) error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoAdapter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Converts the result of a formatting run into an I/O result.
+    pub(crate) fn finish(mut self, result: fmt::Result) -> io::Result<()> {
+        match (result, self.error.take()) {
+            (Ok(()), _) => self.inner.flush(),
+            (Err(_), Some(error)) => Err(error),
+            (Err(_), None) => Err(io::Error::other("formatter error")),
+        }
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
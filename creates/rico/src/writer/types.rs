@@ -6,9 +6,9 @@
 //! - Map types
 //! - User-defined types
 
-use super::Writer;
+use super::{Output, Writer};
 use crate::ast::*;
-use std::fmt::Write;
+use std::fmt::{self, Write};
 
 impl Writer {
     /// Writes a field type to the output string.
@@ -17,26 +17,31 @@ impl Writer {
     /// - Container types (list<T>, set<T>)
     /// - Map types (map<K,V>)
     /// - User-defined types
-    pub(crate) fn write_field_type(&mut self, output: &mut String, field_type: &FieldType) {
+    pub(crate) fn write_field_type(
+        &mut self,
+        output: &mut Output<'_>,
+        field_type: &FieldType,
+    ) -> fmt::Result {
         match field_type {
-            FieldType::CommonType(t) => write!(output, "{}", t.value).unwrap(),
+            FieldType::CommonType(t) => write!(output, "{}", t.value)?,
             FieldType::ListType(t) => {
-                write!(output, "{}<", t.value).unwrap();
-                self.write_field_type(output, &t.value_type);
-                write!(output, ">").unwrap();
+                write!(output, "{}<", t.value)?;
+                self.write_field_type(output, &t.value_type)?;
+                write!(output, ">")?;
             }
             FieldType::SetType(t) => {
-                write!(output, "{}<", t.value).unwrap();
-                self.write_field_type(output, &t.value_type);
-                write!(output, ">").unwrap();
+                write!(output, "{}<", t.value)?;
+                self.write_field_type(output, &t.value_type)?;
+                write!(output, ">")?;
             }
             FieldType::MapType(t) => {
-                write!(output, "{}<", t.value).unwrap();
-                self.write_field_type(output, &t.key_type);
-                write!(output, ", ").unwrap();
-                self.write_field_type(output, &t.value_type);
-                write!(output, ">").unwrap();
+                write!(output, "{}<", t.value)?;
+                self.write_field_type(output, &t.key_type)?;
+                write!(output, ", ")?;
+                self.write_field_type(output, &t.value_type)?;
+                write!(output, ">")?;
             }
         }
+        Ok(())
     }
 }
@@ -0,0 +1,370 @@
+//! Validation of documents before writing.
+//!
+//! Documents built by hand or deserialized from JSON are not guaranteed to
+//! uphold the invariants the parser establishes. The validator walks the AST,
+//! tracks the JSON path of the current node and reports the first violation
+//! as a [`WriterError`].
+
+use super::error::WriterError;
+use crate::ast::string::string_literal_text;
+use crate::ast::*;
+
+type Result<T = ()> = std::result::Result<T, WriterError>;
+
+/// Walks a document and checks node invariants.
+pub(crate) struct Validator {
+    /// JSON path of the node being validated
+    path: String,
+}
+
+impl Validator {
+    pub(crate) fn new() -> Self {
+        Self {
+            path: "$".to_string(),
+        }
+    }
+
+    /// Runs `f` with `segment` appended to the current path.
+    fn scoped(&mut self, segment: &str, f: impl FnOnce(&mut Self) -> Result) -> Result {
+        let len = self.path.len();
+        self.path.push_str(segment);
+        let result = f(self);
+        self.path.truncate(len);
+        result
+    }
+
+    fn key(&mut self, key: &str, f: impl FnOnce(&mut Self) -> Result) -> Result {
+        self.scoped(&format!(".{}", key), f)
+    }
+
+    fn index(&mut self, index: usize, f: impl FnOnce(&mut Self) -> Result) -> Result {
+        self.scoped(&format!("[{}]", index), f)
+    }
+
+    fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    pub(crate) fn validate_document(&mut self, doc: &Document) -> Result {
+        self.key("members", |v| {
+            for (i, member) in doc.members.iter().enumerate() {
+                v.index(i, |v| v.validate_member(member))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn validate_member(&mut self, member: &DocumentMembers) -> Result {
+        match member {
+            DocumentMembers::Namespace(ns) => {
+                self.key("scope", |v| v.validate_identifier(&ns.scope))?;
+                self.key("name", |v| v.validate_identifier(&ns.name))
+            }
+            DocumentMembers::Include(inc) => self.key("name", |v| v.validate_string(&inc.name)),
+            DocumentMembers::Const(c) => {
+                self.key("name", |v| v.validate_identifier(&c.name))?;
+                self.key("fieldType", |v| v.validate_field_type(&c.field_type, false))?;
+                self.key("value", |v| v.validate_value(&c.value))
+            }
+            DocumentMembers::Typedef(td) => {
+                self.key("name", |v| v.validate_identifier(&td.name))?;
+                self.key("fieldType", |v| {
+                    v.validate_field_type(&td.field_type, false)
+                })
+            }
+            DocumentMembers::Enum(e) => {
+                self.key("name", |v| v.validate_identifier(&e.name))?;
+                self.key("members", |v| {
+                    for (i, member) in e.members.iter().enumerate() {
+                        v.index(i, |v| v.validate_enum_member(member))?;
+                    }
+                    Ok(())
+                })?;
+                self.key("annotations", |v| v.validate_annotations(&e.annotations))
+            }
+            DocumentMembers::Struct(s) => {
+                self.validate_struct_like(&s.name, &s.members, &s.annotations)
+            }
+            DocumentMembers::Union(u) => {
+                self.validate_struct_like(&u.name, &u.members, &u.annotations)
+            }
+            DocumentMembers::Exception(e) => {
+                self.validate_struct_like(&e.name, &e.members, &e.annotations)
+            }
+            DocumentMembers::Service(s) => {
+                self.key("name", |v| v.validate_identifier(&s.name))?;
+                if let Some(extends) = &s.extends {
+                    self.key("extends", |v| v.validate_identifier(extends))?;
+                }
+                self.key("members", |v| {
+                    for (i, function) in s.members.iter().enumerate() {
+                        v.index(i, |v| v.validate_function(function))?;
+                    }
+                    Ok(())
+                })?;
+                self.key("annotations", |v| v.validate_annotations(&s.annotations))
+            }
+        }
+    }
+
+    fn validate_struct_like(
+        &mut self,
+        name: &Common<String>,
+        members: &[Field],
+        annotations: &Option<Annotations>,
+    ) -> Result {
+        self.key("name", |v| v.validate_identifier(name))?;
+        self.validate_fields("members", members)?;
+        self.key("annotations", |v| v.validate_annotations(annotations))
+    }
+
+    fn validate_enum_member(&mut self, member: &EnumMember) -> Result {
+        self.key("name", |v| v.validate_identifier(&member.name))?;
+        if let Some(initializer) = &member.initializer {
+            self.key("initializer", |v| {
+                v.key("value", |v| match initializer.value.kind {
+                    NodeType::IntegerLiteral | NodeType::HexLiteral => {
+                        v.validate_literal(&initializer.value)
+                    }
+                    _ => Err(WriterError::InvalidValue {
+                        path: v.path(),
+                        value: initializer.value.value.clone(),
+                    }),
+                })
+            })?;
+        }
+        self.key("annotations", |v| {
+            v.validate_annotations(&member.annotations)
+        })
+    }
+
+    fn validate_function(&mut self, function: &Function) -> Result {
+        self.key("name", |v| v.validate_identifier(&function.name))?;
+        self.key("returnType", |v| {
+            v.validate_field_type(&function.return_type, true)
+        })?;
+        self.validate_fields("params", &function.params)?;
+        if let Some(throws) = &function.throws {
+            self.validate_fields("throws", throws)?;
+        }
+        self.key("annotations", |v| {
+            v.validate_annotations(&function.annotations)
+        })
+    }
+
+    fn validate_fields(&mut self, key: &str, fields: &[Field]) -> Result {
+        self.key(key, |v| {
+            for (i, field) in fields.iter().enumerate() {
+                v.index(i, |v| v.validate_field(field))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn validate_field(&mut self, field: &Field) -> Result {
+        if let Some(id) = &field.field_id {
+            self.key("fieldID", |v| {
+                if id.value.parse::<u64>().is_err() {
+                    return Err(WriterError::InvalidFieldId {
+                        path: v.path(),
+                        value: id.value.clone(),
+                    });
+                }
+                Ok(())
+            })?;
+        }
+        self.key("requiredType", |v| match field.required_type.as_str() {
+            "required" | "optional" | "default" => Ok(()),
+            _ => Err(WriterError::InvalidRequiredType {
+                path: v.path(),
+                value: field.required_type.clone(),
+            }),
+        })?;
+        self.key("fieldType", |v| {
+            v.validate_field_type(&field.field_type, false)
+        })?;
+        self.key("name", |v| v.validate_identifier(&field.name))?;
+        if let Some(default) = &field.default_value {
+            self.key("defaultValue", |v| v.validate_value(default))?;
+        }
+        self.key("annotations", |v| {
+            v.validate_annotations(&field.annotations)
+        })
+    }
+
+    fn validate_field_type(&mut self, field_type: &FieldType, allow_void: bool) -> Result {
+        match field_type {
+            FieldType::CommonType(t) => {
+                let valid_kind = match t.kind {
+                    NodeType::VoidKeyword => allow_void,
+                    kind => is_type_kind(kind),
+                };
+                if !valid_kind {
+                    return Err(WriterError::InvalidType {
+                        path: self.path(),
+                        value: t.value.clone(),
+                    });
+                }
+                self.validate_identifier(t)
+            }
+            FieldType::ListType(t) => {
+                self.validate_container_name(&t.value, "list")?;
+                self.key("valueType", |v| v.validate_field_type(&t.value_type, false))
+            }
+            FieldType::SetType(t) => {
+                self.validate_container_name(&t.value, "set")?;
+                self.key("valueType", |v| v.validate_field_type(&t.value_type, false))
+            }
+            FieldType::MapType(t) => {
+                self.validate_container_name(&t.value, "map")?;
+                self.key("keyType", |v| v.validate_field_type(&t.key_type, false))?;
+                self.key("valueType", |v| v.validate_field_type(&t.value_type, false))
+            }
+        }
+    }
+
+    fn validate_container_name(&mut self, value: &str, expected: &str) -> Result {
+        if value != expected {
+            return Err(WriterError::InvalidType {
+                path: self.path(),
+                value: value.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_value(&mut self, value: &FieldInitialValue) -> Result {
+        match value {
+            FieldInitialValue::ConstValue(v) => self.validate_literal(v),
+            FieldInitialValue::ConstList(list) => self.key("elements", |v| {
+                for (i, element) in list.elements.iter().enumerate() {
+                    v.index(i, |v| v.validate_value(element))?;
+                }
+                Ok(())
+            }),
+            FieldInitialValue::ConstMap(map) => self.key("properties", |v| {
+                for (i, property) in map.properties.iter().enumerate() {
+                    v.index(i, |v| {
+                        v.key("name", |v| v.validate_value(&property.name))?;
+                        v.key("value", |v| v.validate_value(&property.value))
+                    })?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    fn validate_literal(&mut self, literal: &Common<String>) -> Result {
+        let value = literal.value.as_str();
+        let valid = match literal.kind {
+            NodeType::StringLiteral => return self.validate_string(literal),
+            NodeType::Identifier => return self.validate_identifier(literal),
+            NodeType::IntegerLiteral => is_integer(value) && literal.as_i64().is_some(),
+            NodeType::HexLiteral => is_hex(value) && literal.as_i64().is_some(),
+            NodeType::FloatLiteral | NodeType::DoubleConstant => literal.as_f64().is_some(),
+            NodeType::BooleanLiteral => matches!(value, "true" | "false"),
+            _ => false,
+        };
+        if !valid {
+            return Err(WriterError::InvalidValue {
+                path: self.path(),
+                value: value.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_annotations(&mut self, annotations: &Option<Annotations>) -> Result {
+        let Some(annotations) = annotations else {
+            return Ok(());
+        };
+        self.key("members", |v| {
+            for (i, annotation) in annotations.members.iter().enumerate() {
+                v.index(i, |v| {
+                    v.key("name", |v| v.validate_identifier(&annotation.name))?;
+                    v.key("value", |v| v.validate_string(&annotation.value))
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    fn validate_identifier(&mut self, identifier: &Common<String>) -> Result {
+        if identifier.value.is_empty() {
+            return Err(WriterError::EmptyIdentifier { path: self.path() });
+        }
+        if !is_identifier(&identifier.value) {
+            return Err(WriterError::InvalidIdentifier {
+                path: self.path(),
+                value: identifier.value.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Unquoted values are escaped by the writer, quoted values must not
+    /// contain their quote unescaped or end with a backslash. A `decoded`
+    /// text must match the value, since only the value is written.
+    fn validate_string(&mut self, literal: &Common<String>) -> Result {
+        let text = string_literal_text(&literal.value).map_err(|_| WriterError::InvalidValue {
+            path: self.path(),
+            value: literal.value.clone(),
+        })?;
+        match &literal.decoded {
+            Some(decoded) if *decoded != text => Err(WriterError::MismatchedString {
+                path: self.path(),
+                value: literal.value.clone(),
+                decoded: decoded.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Node kinds that may be used for a base or user-defined type.
+fn is_type_kind(kind: NodeType) -> bool {
+    matches!(
+        kind,
+        NodeType::Identifier
+            | NodeType::BaseType
+            | NodeType::BinaryKeyword
+            | NodeType::StringKeyword
+            | NodeType::BoolKeyword
+            | NodeType::ByteKeyword
+            | NodeType::I8Keyword
+            | NodeType::I16Keyword
+            | NodeType::I32Keyword
+            | NodeType::I64Keyword
+            | NodeType::DoubleKeyword
+    )
+}
+
+/// Checks the lexer's identifier grammar: dot separated segments that start
+/// with a letter or underscore.
+fn is_identifier(value: &str) -> bool {
+    value.split('.').all(|segment| {
+        let mut chars = segment.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn strip_sign(value: &str) -> &str {
+    value
+        .strip_prefix('-')
+        .or_else(|| value.strip_prefix('+'))
+        .unwrap_or(value)
+}
+
+fn is_integer(value: &str) -> bool {
+    let digits = strip_sign(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_hex(value: &str) -> bool {
+    let digits = strip_sign(value);
+    digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+        .is_some_and(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -6,9 +6,10 @@
 //! - Map values
 //! - Constant definitions
 
-use super::Writer;
+use super::layout::Doc;
+use super::{Output, Writer};
 use crate::ast::*;
-use std::fmt::Write;
+use std::fmt;
 
 impl Writer {
     /// Writes a field value to the output string.
@@ -16,30 +17,46 @@ impl Writer {
     /// - Constant values (integers, strings, etc.)
     /// - List values ([1, 2, 3])
     /// - Map values ({key: value})
-    pub(crate) fn write_field_value(&mut self, output: &mut String, value: &FieldInitialValue) {
+    ///
+    /// Lists and maps that do not fit in the configured `max_width` are broken
+    /// one element per line.
+    pub(crate) fn write_field_value(
+        &mut self,
+        output: &mut Output<'_>,
+        value: &FieldInitialValue,
+    ) -> fmt::Result {
+        let doc = self.field_value_doc(value);
+        self.write_doc(output, &doc)
+    }
+
+    /// Builds the layout document for a field value.
+    pub(crate) fn field_value_doc(&self, value: &FieldInitialValue) -> Doc {
         match value {
-            FieldInitialValue::ConstValue(v) => write!(output, "{}", v.value).unwrap(),
+            FieldInitialValue::ConstValue(v) if v.kind == NodeType::StringLiteral => {
+                Doc::text(self.string_literal(&v.value))
+            }
+            FieldInitialValue::ConstValue(v) => Doc::text(v.value.as_str()),
             FieldInitialValue::ConstList(list) => {
-                write!(output, "[").unwrap();
-                for (i, element) in list.elements.iter().enumerate() {
-                    if i > 0 {
-                        write!(output, ", ").unwrap();
-                    }
-                    self.write_field_value(output, element);
-                }
-                write!(output, "]").unwrap();
+                let elements = list
+                    .elements
+                    .iter()
+                    .map(|element| self.field_value_doc(element))
+                    .collect();
+                self.bracketed("[", elements, "]")
             }
             FieldInitialValue::ConstMap(map) => {
-                write!(output, "{{").unwrap();
-                for (i, property) in map.properties.iter().enumerate() {
-                    if i > 0 {
-                        write!(output, ", ").unwrap();
-                    }
-                    self.write_field_value(output, &property.name);
-                    write!(output, ": ").unwrap();
-                    self.write_field_value(output, &property.value);
-                }
-                write!(output, "}}").unwrap();
+                let properties = map
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        Doc::Concat(vec![
+                            self.field_value_doc(&property.name),
+                            Doc::text(": "),
+                            self.field_value_doc(&property.value),
+                        ])
+                    })
+                    .collect();
+                self.bracketed("{", properties, "}")
             }
         }
     }
@@ -0,0 +1,59 @@
+use rico::format::{format_check, format_range, unified_diff, TextEdit};
+use rico::writer::WriterOptions;
+
+#[test]
+fn test_format_range_only_touches_overlapping_definitions() {
+    let source =
+        "// first\nstruct A {1: i32 a}\n\nstruct B {1: i32 b}\n\nstruct C {\n  1: i32 c,\n}\n";
+    let b_start = source.find("struct B").unwrap();
+    let c_start = source.find("struct C").unwrap();
+
+    let edits = format_range(source, b_start..c_start + 3, WriterOptions::default()).unwrap();
+
+    assert_eq!(
+        edits,
+        vec![TextEdit {
+            range: b_start..b_start + "struct B {1: i32 b}".len(),
+            new_text: "struct B {\n  1: i32 b,\n}".to_string(),
+        }]
+    );
+
+    let edits = format_range(source, 3..3, WriterOptions::default()).unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, 0);
+    assert_eq!(edits[0].new_text, "// first\nstruct A {\n  1: i32 a,\n}");
+}
+
+#[test]
+fn test_format_check() {
+    let formatted = "typedef i32 A\n\ntypedef i32 B\n\n";
+    assert_eq!(format_check(formatted).unwrap(), "");
+
+    let source = "typedef i32 A\n\ntypedef   i32 B\n\n";
+    assert_eq!(
+        format_check(source).unwrap(),
+        "--- original\n+++ formatted\n@@ -1,4 +1,4 @@\n typedef i32 A\n \n-typedef   i32 B\n+typedef i32 B\n \n"
+    );
+
+    assert!(format_check("struct {").is_err());
+}
+
+#[test]
+fn test_unified_diff_of_large_inputs() {
+    let old: String = (0..100_000).map(|i| format!("old {}\n", i)).collect();
+    let new: String = (0..100_000).map(|i| format!("new {}\n", i)).collect();
+
+    let diff = unified_diff(&old, &new, "original", "formatted");
+    let lines: Vec<&str> = diff.lines().collect();
+    assert_eq!(lines.len(), 3 + 200_000);
+    assert_eq!(lines[2], "@@ -1,100000 +1,100000 @@");
+    assert_eq!(lines[3], "-old 0");
+    assert_eq!(lines[100_002], "-old 99999");
+    assert_eq!(lines[100_003], "+new 0");
+
+    let mut moved: Vec<&str> = old.split_inclusive('\n').collect();
+    moved.swap(10, 50_000);
+    let diff = unified_diff(&old, &moved.concat(), "original", "formatted");
+    assert_eq!(diff.lines().filter(|line| line.starts_with('-')).count(), 3);
+    assert_eq!(diff.lines().filter(|line| line.starts_with('+')).count(), 3);
+}
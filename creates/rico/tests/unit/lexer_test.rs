@@ -1,5 +1,6 @@
 use logos::Logos;
-use rico::lexer::Token;
+use rico::lexer::{tokenize, Token};
+use rico::line_index::PositionEncoding;
 
 #[test]
 fn test_lexer() {
@@ -25,3 +26,78 @@ fn test_lexer() {
     assert_eq!(lexer.next(), Some(Ok(Token::Identifier)));
     assert_eq!(lexer.next(), Some(Ok(Token::RightBrace)));
 }
+
+#[test]
+fn test_lexer_line_breaks() {
+    let input = "// a\r\nstruct\rA\x0c{\n/* b\r\n c */ }";
+    let tokens: Vec<_> = tokenize(input)
+        .map(|(token, text, loc)| (token, text, (loc.start.line, loc.start.column)))
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (Token::LineComment, "// a", (1, 1)),
+            (Token::Struct, "struct", (2, 1)),
+            (Token::Identifier, "A", (3, 1)),
+            // A form feed is whitespace, not a line break
+            (Token::LeftBrace, "{", (3, 3)),
+            (Token::BlockComment, "/* b\r\n c */", (4, 1)),
+            (Token::RightBrace, "}", (5, 7)),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize() {
+    let input = "/* ünïcode */ const i32 X = @1\r\n$";
+    let tokens: Vec<_> = tokenize(input)
+        .with_position_encoding(PositionEncoding::Utf16)
+        .map(|(token, text, loc)| {
+            (
+                token,
+                text,
+                (loc.start.line, loc.start.column),
+                (loc.end.line, loc.end.column),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (Token::BlockComment, "/* ünïcode */", (1, 1), (1, 14)),
+            (Token::Const, "const", (1, 15), (1, 20)),
+            (Token::I32, "i32", (1, 21), (1, 24)),
+            (Token::Identifier, "X", (1, 25), (1, 26)),
+            (Token::Equals, "=", (1, 27), (1, 28)),
+            (Token::Error, "@", (1, 29), (1, 30)),
+            (Token::IntegerLiteral, "1", (1, 30), (1, 31)),
+            (Token::Error, "$", (2, 1), (2, 2)),
+        ]
+    );
+
+    let last = tokenize(input).last().unwrap().2;
+    assert_eq!(last.start.index, input.len() - 1);
+}
+
+#[test]
+fn test_lexer_numeric_literals() {
+    let tokens: Vec<_> = tokenize("1e10 5E-3 .5 -1.5e+3 -0x1F +0X1f 42 -7")
+        .map(|(token, text, _)| (token, text))
+        .collect();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (Token::DoubleLiteral, "1e10"),
+            (Token::DoubleLiteral, "5E-3"),
+            (Token::DoubleLiteral, ".5"),
+            (Token::DoubleLiteral, "-1.5e+3"),
+            (Token::HexLiteral, "-0x1F"),
+            (Token::HexLiteral, "+0X1f"),
+            (Token::IntegerLiteral, "42"),
+            (Token::IntegerLiteral, "-7"),
+        ]
+    );
+}
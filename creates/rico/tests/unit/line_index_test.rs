@@ -0,0 +1,96 @@
+use rico::line_index::{LineIndex, PositionEncoding};
+use rico::{DocumentMembers, Parser};
+
+#[test]
+fn test_line_index_line_breaks() {
+    let source = "a\r\nb\rc\nd";
+    let index = LineIndex::new(source);
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.line_text(1), Some("a"));
+    assert_eq!(index.line_text(2), Some("b"));
+    assert_eq!(index.line_text(3), Some("c"));
+    assert_eq!(index.line_text(4), Some("d"));
+    assert_eq!(index.line_text(5), None);
+
+    let d = index.position(source.find('d').unwrap(), PositionEncoding::Utf8);
+    assert_eq!((d.line, d.column), (4, 1));
+}
+
+#[test]
+fn test_line_index_columns_by_encoding() {
+    // `é` is two UTF-8 bytes and one UTF-16 unit, `🦀` four bytes and two units
+    let source = "é🦀x";
+    let index = LineIndex::new(source);
+    let x = source.find('x').unwrap();
+
+    assert_eq!(index.position(x, PositionEncoding::Utf8).column, 7);
+    assert_eq!(index.position(x, PositionEncoding::Utf16).column, 4);
+    assert_eq!(index.position(x, PositionEncoding::Utf32).column, 3);
+
+    for encoding in [
+        PositionEncoding::Utf8,
+        PositionEncoding::Utf16,
+        PositionEncoding::Utf32,
+    ] {
+        let span = index.position(x, encoding);
+        assert_eq!(index.offset(span.line, span.column, encoding), Some(x));
+    }
+}
+
+#[test]
+fn test_line_index_clamps_offsets() {
+    let source = "🦀\nab";
+    let index = LineIndex::new(source);
+
+    // Inside the crab, and past the end
+    assert_eq!(index.position(2, PositionEncoding::Utf8).index, 0);
+    assert_eq!(
+        index.position(100, PositionEncoding::Utf8).index,
+        source.len()
+    );
+    // Inside a surrogate pair, and past the end of the line
+    assert_eq!(index.offset(1, 2, PositionEncoding::Utf16), Some(0));
+    assert_eq!(
+        index.offset(2, 10, PositionEncoding::Utf16),
+        Some(source.len())
+    );
+    assert_eq!(index.offset(3, 1, PositionEncoding::Utf16), None);
+}
+
+#[test]
+fn test_parser_positions_after_multiline_comment() {
+    let source = "/* a\n   b */ const string S = \"日本\"\r\nconst i32 N = 1";
+    let document = Parser::new(source)
+        .with_position_encoding(PositionEncoding::Utf16)
+        .parse()
+        .unwrap();
+
+    let DocumentMembers::Const(s) = &document.members[0] else {
+        panic!("expected a const");
+    };
+    let comment = &s.comments[0];
+    assert_eq!((comment.loc.end.line, comment.loc.end.column), (2, 8));
+    assert_eq!((s.loc.start.line, s.loc.start.column), (2, 9));
+    assert_eq!((s.loc.end.line, s.loc.end.column), (2, 30));
+
+    let DocumentMembers::Const(n) = &document.members[1] else {
+        panic!("expected a const");
+    };
+    assert_eq!((n.loc.start.line, n.loc.start.column), (3, 1));
+}
+
+#[test]
+fn test_parser_positions_with_crlf() {
+    let source = "// user\r\nstruct User {\r\n  1: string name\r\n}\r\n";
+    let document = Parser::new(source).parse().unwrap();
+
+    let DocumentMembers::Struct(user) = &document.members[0] else {
+        panic!("expected a struct");
+    };
+    assert_eq!(user.comments[0].value, "// user");
+    assert_eq!((user.loc.start.line, user.loc.start.column), (2, 1));
+    assert_eq!((user.loc.end.line, user.loc.end.column), (4, 2));
+    let name = &user.members[0].name;
+    assert_eq!((name.loc.start.line, name.loc.start.column), (3, 13));
+}
@@ -1,3 +1,6 @@
 mod ast_test;
+mod format_test;
 mod lexer_test;
+mod line_index_test;
 mod parser_test;
+mod writer_test;
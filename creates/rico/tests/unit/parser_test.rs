@@ -1,7 +1,10 @@
 
+    use miette::Diagnostic;
+    use rico::parser::ParseWarning;
     use rico::DocumentMembers;
     use rico::FieldInitialValue;
     use rico::FieldType;
+    use rico::NodeType;
     use rico::Parser;
 
     #[test]
@@ -190,3 +193,213 @@
         }
     }
 
+    #[test]
+    fn test_parse_numeric_literals() {
+        let input = r#"
+            const double A = 1e10
+            const double B = 5E-3
+            const double C = -.5e+2
+            const i64 D = -0x1F
+            const i64 E = +0X10
+            const i64 F = -9223372036854775808
+        "#;
+        let result = Parser::new(input).parse().unwrap();
+
+        let values: Vec<_> = result
+            .members
+            .iter()
+            .map(|member| match member {
+                DocumentMembers::Const(c) => match &c.value {
+                    FieldInitialValue::ConstValue(v) => (v.kind, v.as_i64(), v.as_f64()),
+                    _ => panic!("Expected ConstValue"),
+                },
+                _ => panic!("Expected Const"),
+            })
+            .collect();
+
+        assert_eq!(
+            values,
+            vec![
+                (NodeType::FloatLiteral, None, Some(1e10)),
+                (NodeType::FloatLiteral, None, Some(5e-3)),
+                (NodeType::FloatLiteral, None, Some(-50.0)),
+                (NodeType::HexLiteral, Some(-31), Some(-31.0)),
+                (NodeType::HexLiteral, Some(16), Some(16.0)),
+                (
+                    NodeType::IntegerLiteral,
+                    Some(i64::MIN),
+                    Some(i64::MIN as f64)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_numeric_overflow() {
+        for (input, literal) in [
+            ("const i64 A = 0xFFFFFFFFFFFFFFFFFF", "0xFFFFFFFFFFFFFFFFFF"),
+            ("const i64 A = 99999999999999999999", "99999999999999999999"),
+            ("const i64 A = 9223372036854775808", "9223372036854775808"),
+            ("const double A = 1e999", "1e999"),
+            ("enum E { A = 0x10000000000000000 }", "0x10000000000000000"),
+            (
+                "const list<i64> A = [1, -99999999999999999999]",
+                "-99999999999999999999",
+            ),
+        ] {
+            let error = Parser::new(input).parse().unwrap_err();
+            assert_eq!(
+                error.code().unwrap().to_string(),
+                "rico::parser::numeric_overflow"
+            );
+            let label = error.labels().unwrap().next().unwrap();
+            assert_eq!(
+                &input[label.offset()..label.offset() + label.len()],
+                literal
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let input = r#"
+            const string A = "tab\t \"quoted\" \u00e9 \ud83d\ude00"
+            const map<string, string> B = {'it\'s': "a\\b"}
+            struct S { 1: string name (label = "line\nbreak") }
+        "#;
+        let document = Parser::new(input).parse().unwrap();
+        let json = serde_json::to_value(&document).unwrap();
+
+        let a = &json["members"][0]["value"];
+        assert_eq!(a["value"], r#""tab\t \"quoted\" \u00e9 \ud83d\ude00""#);
+        assert_eq!(a["decoded"], "tab\t \"quoted\" \u{e9} \u{1f600}");
+        let b = &json["members"][1]["value"]["properties"][0];
+        assert_eq!(b["name"]["decoded"], "it's");
+        assert_eq!(b["value"]["decoded"], "a\\b");
+        let label = &json["members"][2]["members"][0]["annotations"]["members"][0];
+        assert_eq!(label["value"]["decoded"], "line\nbreak");
+        assert!(json["members"][0]["name"].get("decoded").is_none());
+
+        for (input, escape, decoded) in [
+            (r#"const string A = "a\qb""#, "\\q", "a\\qb"),
+            (r#"const string A = "\u12""#, "\\u", "\\u12"),
+            (r#"const string A = "\ud83d""#, "\\ud83d", "\\ud83d"),
+        ] {
+            let mut parser = Parser::new(input);
+            let document = parser.parse().unwrap();
+            let json = serde_json::to_value(&document).unwrap();
+            assert_eq!(json["members"][0]["value"]["decoded"], decoded);
+
+            let [warning] = parser.warnings() else {
+                panic!("expected one warning for {}", input);
+            };
+            assert_eq!(
+                warning.code().unwrap().to_string(),
+                "rico::parser::invalid_escape"
+            );
+            let label = warning.labels().unwrap().next().unwrap();
+            assert_eq!(&input[label.offset()..label.offset() + label.len()], escape);
+        }
+    }
+
+    #[test]
+    fn test_parse_go_tag_with_invalid_escapes() {
+        let input = r#"struct S { 1: string id (go.tag = "validate:\"regexp=^\d+$\"") }"#;
+        let mut parser = Parser::new(input);
+        let document = parser.parse().unwrap();
+        let json = serde_json::to_value(&document).unwrap();
+
+        let value = &json["members"][0]["members"][0]["annotations"]["members"][0]["value"];
+        assert_eq!(value["value"], r#""validate:\"regexp=^\d+$\"""#);
+        assert_eq!(value["decoded"], r#"validate:"regexp=^\d+$""#);
+        assert_eq!(parser.warnings().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_keywords_as_names() {
+        let input = r#"
+            namespace rs service
+            const i32 binary = 1
+            typedef string void
+            enum set { union, exception = 2, const }
+            struct map { 1: i32 i32, 2: binary binary, 3: string void (list = "1") }
+            service oneway extends service {
+                void throws(1: string required) throws (1: Error exception)
+            }
+        "#;
+        let document = Parser::new(input).parse().unwrap();
+        let json = serde_json::to_value(&document).unwrap();
+
+        assert_eq!(json["members"][0]["name"]["value"], "service");
+        assert_eq!(json["members"][1]["name"]["value"], "binary");
+        assert_eq!(json["members"][2]["name"]["value"], "void");
+        let members = &json["members"][3]["members"];
+        assert_eq!(members[0]["name"]["value"], "union");
+        assert_eq!(members[2]["name"]["value"], "const");
+        let fields = &json["members"][4]["members"];
+        assert_eq!(fields[0]["name"]["value"], "i32");
+        assert_eq!(fields[1]["name"]["value"], "binary");
+        assert_eq!(
+            fields[2]["annotations"]["members"][0]["name"]["value"],
+            "list"
+        );
+        let function = &json["members"][5]["members"][0];
+        assert_eq!(json["members"][5]["extends"]["value"], "service");
+        assert_eq!(function["name"]["value"], "throws");
+        assert_eq!(function["params"][0]["name"]["value"], "required");
+        assert_eq!(function["throws"][0]["name"]["value"], "exception");
+
+        let mut parser = Parser::new(input);
+        parser.parse().unwrap();
+        assert!(parser.warnings().is_empty());
+
+        let mut parser = Parser::new(input).with_strict(true);
+        parser.parse().unwrap();
+        let keywords: Vec<_> = parser
+            .warnings()
+            .iter()
+            .map(|warning| match warning {
+                ParseWarning::KeywordAsName { keyword, span } => {
+                    assert_eq!(&input[span.offset()..span.offset() + span.len()], keyword);
+                    keyword.as_str()
+                }
+                warning => panic!("unexpected warning {:?}", warning),
+            })
+            .collect();
+        assert_eq!(
+            keywords,
+            [
+                "service",
+                "binary",
+                "void",
+                "set",
+                "union",
+                "exception",
+                "const",
+                "map",
+                "i32",
+                "binary",
+                "void",
+                "list",
+                "oneway",
+                "service",
+                "throws",
+                "required",
+                "exception"
+            ]
+        );
+        assert_eq!(
+            parser.warnings()[0].code().unwrap().to_string(),
+            "rico::parser::keyword_as_name"
+        );
+        assert_eq!(
+            parser.warnings()[0].severity(),
+            Some(miette::Severity::Warning)
+        );
+
+        let error = Parser::new("struct S { 1: i32 = 1 }").parse().unwrap_err();
+        assert_eq!(
+            error.code().unwrap().to_string(),
+            "rico::parser::invalid_field_name"
+        );
+    }
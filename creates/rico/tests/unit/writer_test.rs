@@ -0,0 +1,349 @@
+use rico::writer::{Indent, Separator, Writer, WriterError, WriterOptions};
+use rico::{DocumentMembers, Parser};
+
+fn format(input: &str, options: WriterOptions) -> String {
+    let mut parser = Parser::new(input);
+    let document = parser.parse().unwrap();
+    Writer::with_options(options).write(&document)
+}
+
+#[test]
+fn test_write_compact_when_fits() {
+    let input = r#"const list<string> NAMES = ["a", "b"]"#;
+    let output = format(
+        input,
+        WriterOptions {
+            max_width: Some(80),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(output, "const list<string> NAMES = [\"a\", \"b\"]\n\n");
+}
+
+#[test]
+fn test_write_breaks_long_const_literals() {
+    let input = r#"
+        const map<string, list<i32>> GROUPS = {"first": [1, 2, 3], "second": [4, 5, 6]}
+    "#;
+    let output = format(
+        input,
+        WriterOptions {
+            max_width: Some(40),
+            trailing_comma: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        output,
+        r#"const map<string, list<i32>> GROUPS = {
+  "first": [1, 2, 3],
+  "second": [4, 5, 6],
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_write_breaks_long_parameter_lists() {
+    let input = r#"
+        service UserService {
+            User createUser(1: string name, 2: i32 age, 3: string email) throws (1: Error e)
+        }
+    "#;
+    let output = format(
+        input,
+        WriterOptions {
+            max_width: Some(60),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        output,
+        r#"service UserService {
+  User createUser(
+    1: string name,
+    2: i32 age,
+    3: string email
+  ) throws (1: Error e)
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_write_canonical() {
+    let canonical = WriterOptions {
+        canonical: true,
+        ..Default::default()
+    };
+    let first = format(
+        r#"
+        namespace rs demo
+        include 'shared.thrift'
+        namespace java com.demo
+        include "base.thrift"
+
+        enum Status {
+            OK = 0x1,
+            ERROR = 2;
+        }
+
+        struct User {
+            2: string name = 'it\'s "me"',
+            1: i32 id;
+        }
+        "#,
+        canonical.clone(),
+    );
+    let second = format(
+        r#"
+        include "shared.thrift"
+        include "base.thrift"
+        namespace java com.demo
+        namespace rs demo
+        enum Status { OK = 1 ERROR = 2 }
+        struct User { 1: i32 id 2: string name = "it's \"me\"" }
+        "#,
+        canonical,
+    );
+
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        r#"include "base.thrift"
+
+include "shared.thrift"
+
+namespace java com.demo
+
+namespace rs demo
+
+enum Status {
+  OK = 1
+  ERROR = 2
+}
+
+struct User {
+  1: i32 id
+  2: string name = "it's \"me\""
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_write_with_tabs_and_semicolons() {
+    let input = r#"
+        struct User { 1: i32 id, 2: list<string> tags = ["admin", "owner"] }
+        enum Role { ADMIN = 1, USER = 2 }
+    "#;
+    let output = format(
+        input,
+        WriterOptions {
+            max_width: Some(30),
+            indent: Indent::Tabs,
+            separator: Separator::Semicolon,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        output,
+        "struct User {\n\t1: i32 id;\n\t2: list<string> tags = [\n\t\t\"admin\",\n\t\t\"owner\"\n\t];\n}\n\nenum Role {\n\tADMIN = 1;\n\tUSER = 2;\n}\n\n"
+    );
+}
+
+#[test]
+fn test_write_with_four_spaces_without_separators() {
+    let output = format(
+        "struct User { 1: i32 id, 2: string name }",
+        WriterOptions {
+            indent: Indent::Spaces(4),
+            separator: Separator::None,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        output,
+        "struct User {\n    1: i32 id\n    2: string name\n}\n\n"
+    );
+}
+
+#[test]
+fn test_write_to_io_sink() {
+    let input = r#"
+        struct User {
+            1: string name
+        }
+    "#;
+    let document = Parser::new(input).parse().unwrap();
+    let mut writer = Writer::new();
+
+    let mut buffer = Vec::new();
+    writer.write_to(&document, &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), writer.write(&document));
+
+    let mut full = [0u8; 8];
+    let error = writer
+        .write_to(&document, &mut full[..])
+        .expect_err("sink is too small");
+    assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_write_to_failing_sink_at_field_modifiers() {
+    let document = Parser::new("struct User { 1: required string name, 2: optional i32 age }")
+        .parse()
+        .unwrap();
+    let mut writer = Writer::new();
+    let output = writer.write(&document);
+
+    // The sink fills up in the middle of each modifier
+    for modifier in ["required ", "optional "] {
+        let mut sink = vec![0u8; output.find(modifier).unwrap() + 3];
+        let error = writer
+            .write_to(&document, &mut sink[..])
+            .expect_err("sink is too small");
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+}
+
+#[test]
+fn test_write_single_node() {
+    let input = r#"
+        service UserService {
+            map<string, list<i32>> stats(1: i32 id = 1)
+        }
+    "#;
+    let document = Parser::new(input).parse().unwrap();
+    let mut writer = Writer::new();
+
+    match &document.members[0] {
+        DocumentMembers::Service(s) => {
+            let function = &s.members[0];
+            assert_eq!(
+                writer.write_node(&function.return_type),
+                "map<string, list<i32>>"
+            );
+            assert_eq!(
+                writer.write_node(function),
+                "map<string, list<i32>> stats(1: i32 id = 1)\n"
+            );
+            assert_eq!(
+                writer.write_node(s),
+                "service UserService {\n  map<string, list<i32>> stats(1: i32 id = 1)\n}\n"
+            );
+        }
+        _ => panic!("Expected Service"),
+    }
+}
+
+#[test]
+fn test_try_write_validates_documents() {
+    let input = r#"
+        const string NAME = "rico"
+        struct User {
+            1: string name
+            2: i32 age
+        }
+    "#;
+    let document = Parser::new(input).parse().unwrap();
+    let mut writer = Writer::new();
+    assert_eq!(
+        writer.try_write(&document).unwrap(),
+        writer.write(&document)
+    );
+
+    let json = serde_json::to_string(&document).unwrap();
+    let invalid = json
+        .replace(r#""value":"2""#, r#""value":"two""#)
+        .replace(r#"\"rico\""#, r#"\"ri\"co\""#);
+    let document: rico::Document = serde_json::from_str(&invalid).unwrap();
+
+    let error = writer.try_write(&document).unwrap_err();
+    assert_eq!(
+        error,
+        WriterError::InvalidValue {
+            path: "$.members[0].value".to_string(),
+            value: r#""ri"co""#.to_string(),
+        }
+    );
+
+    let invalid = json.replace(r#""value":"2""#, r#""value":"two""#);
+    let document: rico::Document = serde_json::from_str(&invalid).unwrap();
+    let error = writer.try_write(&document).unwrap_err();
+    assert_eq!(error.path(), "$.members[1].members[1].fieldID");
+}
+
+#[test]
+fn test_write_quotes_unquoted_strings() {
+    let mut document = Parser::new(r#"const string NAME = "rico""#)
+        .parse()
+        .unwrap();
+    if let DocumentMembers::Const(c) = &mut document.members[0] {
+        if let rico::FieldInitialValue::ConstValue(value) = &mut c.value {
+            value.value = "say \"hi\"\n\tC:\\".to_string();
+            value.decoded = None;
+        }
+    }
+
+    let output = Writer::new().try_write(&document).unwrap();
+    assert_eq!(
+        output,
+        concat!(r#"const string NAME = "say \"hi\"\n\tC:\\""#, "\n\n")
+    );
+}
+
+#[test]
+fn test_write_keeps_string_literals() {
+    for input in [
+        r#"const string A = 'Hello'"#,
+        r#"const string A = "it's \u00e9""#,
+        r#"const string A = "validate:\"regexp=^\d+$\"""#,
+    ] {
+        let document = Parser::new(input).parse().unwrap();
+        let output = Writer::new().try_write(&document).unwrap();
+        assert_eq!(output, format!("{}\n\n", input));
+    }
+}
+
+#[test]
+fn test_write_rejects_mismatched_strings() {
+    let edit = |value: &str, decoded: Option<&str>| {
+        let mut document = Parser::new(r#"const string A = "old""#).parse().unwrap();
+        if let DocumentMembers::Const(c) = &mut document.members[0] {
+            if let rico::FieldInitialValue::ConstValue(literal) = &mut c.value {
+                literal.value = value.to_string();
+                literal.decoded = decoded.map(str::to_string);
+            }
+        }
+        document
+    };
+    let mut writer = Writer::new();
+
+    // Editing only one of the two fields is reported instead of guessed
+    for document in [
+        edit(r#""new""#, Some("old")),
+        edit("new", Some("old")),
+        edit(r#""old""#, Some("new")),
+    ] {
+        let error = writer.try_write(&document).unwrap_err();
+        assert_eq!(error.path(), "$.members[0].value");
+        assert!(matches!(error, WriterError::MismatchedString { .. }));
+    }
+
+    // `value` is written when both agree or `decoded` is removed
+    for document in [edit(r#""new""#, Some("new")), edit(r#""new""#, None)] {
+        assert_eq!(
+            writer.try_write(&document).unwrap(),
+            "const string A = \"new\"\n\n"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Checks that the TypeScript declarations emitted into the WASM module are
+//! self-contained, since `tsc` skips checking `.d.ts` files with `skipLibCheck`.
+
+use std::collections::BTreeSet;
+
+use rico::ast::*;
+use rico::line_index::PositionEncoding;
+use tsify::Tsify;
+
+use crate::error::{Location, RicoError};
+use crate::{
+    FileDiagnostics, FormatOptions, FormatSeparator, ParsedFile, WorkspaceParseResult,
+    WorkspaceValidationResult,
+};
+
+/// Types provided by TypeScript itself
+const BUILTINS: &[&str] = &["Array", "Record", "Partial"];
+
+/// Global TypeScript types that declarations must not shadow
+const GLOBALS: &[&str] = &[
+    "Array", "Boolean", "Date", "Error", "Function", "Map", "Number", "Object", "Promise",
+    "RegExp", "Set", "String", "Symbol",
+];
+
+fn declarations() -> Vec<&'static str> {
+    vec![
+        Comment::DECL,
+        Annotation::DECL,
+        Annotations::DECL,
+        FieldListType::DECL,
+        FieldSetType::DECL,
+        FieldMapType::DECL,
+        ConstList::DECL,
+        MapProperty::DECL,
+        ConstMap::DECL,
+        FieldInitialValue::DECL,
+        FieldType::DECL,
+        Initializer::DECL,
+        EnumMember::DECL,
+        Field::DECL,
+        Function::DECL,
+        Namespace::DECL,
+        Include::DECL,
+        Const::DECL,
+        Typedef::DECL,
+        Enum::DECL,
+        Exception::DECL,
+        Struct::DECL,
+        Union::DECL,
+        Service::DECL,
+        Document::DECL,
+        DocumentMembers::DECL,
+        Span::DECL,
+        LOC::DECL,
+        <Common as Tsify>::DECL,
+        NodeType::DECL,
+        PositionEncoding::DECL,
+        Location::DECL,
+        RicoError::DECL,
+        FormatOptions::DECL,
+        FormatSeparator::DECL,
+        ParsedFile::DECL,
+        FileDiagnostics::DECL,
+        WorkspaceParseResult::DECL,
+        WorkspaceValidationResult::DECL,
+    ]
+}
+
+/// Returns the identifiers of `decl` outside string literals
+fn identifiers(decl: &str) -> Vec<&str> {
+    decl.split('"')
+        .step_by(2)
+        .flat_map(|code| code.split(|c: char| !c.is_ascii_alphanumeric() && c != '_'))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Returns the name declared by `decl`
+fn declared_name(decl: &str) -> &str {
+    let words = identifiers(decl);
+    let keyword = words
+        .iter()
+        .position(|word| matches!(*word, "interface" | "type"))
+        .unwrap_or_else(|| panic!("no declaration in {}", decl));
+    words[keyword + 1]
+}
+
+#[test]
+fn test_declarations_are_self_contained() {
+    let declarations = declarations();
+    let declared: BTreeSet<&str> = declarations
+        .iter()
+        .map(|decl| declared_name(decl))
+        .collect();
+
+    let shadowed: Vec<_> = declared
+        .iter()
+        .filter(|name| GLOBALS.contains(name))
+        .collect();
+    assert!(
+        shadowed.is_empty(),
+        "global types are shadowed: {:?}",
+        shadowed
+    );
+
+    // Type names start with an uppercase letter, except the parameter of `Common<T>`
+    let unresolved: BTreeSet<_> = declarations
+        .iter()
+        .flat_map(|decl| identifiers(decl))
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_uppercase()) && *word != "T")
+        .filter(|word| !declared.contains(word) && !BUILTINS.contains(word))
+        .collect();
+    assert!(unresolved.is_empty(), "unresolved types: {:?}", unresolved);
+}
@@ -1,10 +1,20 @@
 use std::error::Error;
 
+use js_sys::Object;
 use miette::{Diagnostic, LabeledSpan};
+use rico::line_index::{LineIndex, PositionEncoding};
+use rico::parser::ParseWarning;
+use rico::writer::WriterError;
 use serde::Serialize;
 use serde_json;
+use serde_wasm_bindgen::Serializer;
+use tsify::Tsify;
+use wasm_bindgen::{JsCast, JsValue};
 
-#[derive(Serialize)]
+/// The position of an error, with the column and length counted in the
+/// position encoding of the parser
+#[derive(Serialize, Tsify, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct Location {
     line: usize,
     column: usize,
@@ -12,7 +22,7 @@ pub struct Location {
     source_text: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Tsify, Debug)]
 #[serde(tag = "kind")]
 pub enum RicoError {
     #[serde(rename = "ParseError")]
@@ -22,6 +32,27 @@ pub enum RicoError {
         help: Option<String>,
         location: Option<Location>,
     },
+    #[serde(rename = "ParseWarning")]
+    Warning {
+        message: String,
+        code: String,
+        help: Option<String>,
+        location: Option<Location>,
+    },
+    #[serde(rename = "WriteError")]
+    Write {
+        message: String,
+        code: String,
+        help: Option<String>,
+        path: String,
+    },
+    #[serde(rename = "IncludeError")]
+    Include {
+        message: String,
+        code: String,
+        help: Option<String>,
+        location: Option<Location>,
+    },
     #[serde(rename = "SerializationError")]
     Serialization { message: String, code: String },
     #[serde(rename = "DeserializationError")]
@@ -43,7 +74,49 @@ impl RicoError {
         }
     }
 
-    pub fn parse(e: impl Diagnostic, source: &str) -> Self {
+    pub fn write(e: WriterError) -> Self {
+        Self::Write {
+            message: e.to_string(),
+            code: e
+                .code()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "WRITE_ERROR".to_string()),
+            help: e.help().map(|s| s.to_string()),
+            path: e.path().to_string(),
+        }
+    }
+
+    /// An include whose file is not in the workspace
+    /// `offset` and `length` are the byte span of the include path in `source`
+    pub fn unresolved_include(
+        path: &str,
+        offset: usize,
+        length: usize,
+        source: &str,
+        encoding: PositionEncoding,
+    ) -> Self {
+        Self::Include {
+            message: format!("Unresolved include {}", path),
+            code: "UNRESOLVED_INCLUDE".to_string(),
+            help: Some(
+                "Add the file to the workspace, relative to this file or to the workspace root"
+                    .to_string(),
+            ),
+            location: Some(calculate_location(offset, length, source, encoding)),
+        }
+    }
+
+    /// A workspace path that leaves the workspace root
+    pub fn invalid_path(path: &str) -> Self {
+        Self::Include {
+            message: format!("Path {} is outside of the workspace", path),
+            code: "INVALID_PATH".to_string(),
+            help: Some("Remove the `..` segments that leave the workspace root".to_string()),
+            location: None,
+        }
+    }
+
+    pub fn parse(e: impl Diagnostic, source: &str, encoding: PositionEncoding) -> Self {
         Self::Parse {
             message: e.to_string(),
             code: e
@@ -51,7 +124,19 @@ impl RicoError {
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "PARSE_ERROR".to_string()),
             help: e.help().map(|s| s.to_string()),
-            location: get_error_location(&e, source),
+            location: get_error_location(&e, source, encoding),
+        }
+    }
+
+    pub fn warning(w: &ParseWarning, source: &str, encoding: PositionEncoding) -> Self {
+        Self::Warning {
+            message: w.to_string(),
+            code: w
+                .code()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "PARSE_WARNING".to_string()),
+            help: w.help().map(|s| s.to_string()),
+            location: get_error_location(w, source, encoding),
         }
     }
 
@@ -60,36 +145,70 @@ impl RicoError {
     }
 }
 
-fn get_error_location(e: &impl Diagnostic, source: &str) -> Option<Location> {
+impl RicoError {
+    /// The `kind` tag of the error
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Parse { .. } => "ParseError",
+            Self::Warning { .. } => "ParseWarning",
+            Self::Write { .. } => "WriteError",
+            Self::Include { .. } => "IncludeError",
+            Self::Serialization { .. } => "SerializationError",
+            Self::Deserialization { .. } => "DeserializationError",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::Parse { message, .. }
+            | Self::Warning { message, .. }
+            | Self::Write { message, .. }
+            | Self::Include { message, .. }
+            | Self::Serialization { message, .. }
+            | Self::Deserialization { message, .. } => message,
+        }
+    }
+
+    /// Converts the error into a JS `Error` named after its kind, carrying
+    /// the error fields as properties
+    pub fn into_js(self) -> JsValue {
+        let error = js_sys::Error::new(self.message());
+        error.set_name(self.kind());
+        if let Ok(fields) = self.serialize(&Serializer::json_compatible()) {
+            Object::assign(&error, fields.unchecked_ref());
+        }
+        error.into()
+    }
+}
+
+fn get_error_location(
+    e: &impl Diagnostic,
+    source: &str,
+    encoding: PositionEncoding,
+) -> Option<Location> {
     e.labels()
         .into_iter()
         .flatten()
         .next()
-        .map(|label: LabeledSpan| calculate_location(label.offset(), label.len(), source))
+        .map(|label: LabeledSpan| calculate_location(label.offset(), label.len(), source, encoding))
 }
 
-fn calculate_location(offset: usize, length: usize, source: &str) -> Location {
-    let lines: Vec<&str> = source.lines().collect();
-
-    // Calculate line number and column by counting newlines
-    let mut line = 1;
-    let mut last_newline = 0;
-    let mut pos = 0;
-    for (i, c) in source[..offset].chars().enumerate() {
-        if c == '\n' {
-            line += 1;
-            last_newline = i + 1;
-        }
-        pos = i;
-    }
-    let column = pos - last_newline + 1;
+fn calculate_location(
+    offset: usize,
+    length: usize,
+    source: &str,
+    encoding: PositionEncoding,
+) -> Location {
+    let index = LineIndex::new(source);
+    let start = index.position(offset, encoding);
+    let end = index.position(offset + length, encoding);
 
     Location {
-        line,
-        column,
-        length,
-        source_text: lines
-            .get(line - 1)
+        line: start.line,
+        column: start.column,
+        length: encoding.len(&source[start.index..end.index]),
+        source_text: index
+            .line_text(start.line)
             .map(|s| s.to_string())
             .unwrap_or_default(),
     }
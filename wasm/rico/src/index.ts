@@ -1,12 +1,13 @@
-import init, { Parser, Writer } from './wasm/rico_wasm';
-import type { Document, ParseError } from './types';
+import init, { format, Parser, Writer } from './wasm/rico_wasm';
+import type { Document, FormatOptions, ParseError } from './types';
 
 export class RicoError extends Error {
   constructor(public details: ParseError) {
     const message = [
       `${details.message}${
-        details.location &&
-        `(${details.location.line}:${details.location.column})`
+        details.location
+          ? ` (${details.location.line}:${details.location.column})`
+          : ''
       }`,
       details.help && `Help: ${details.help}`
     ]
@@ -20,6 +21,29 @@ export class RicoError extends Error {
   }
 }
 
+/**
+ * Rethrows errors of the WASM module as `RicoError`.
+ * The object APIs throw `Error` objects carrying the error fields, while the
+ * string APIs throw the fields serialized as JSON.
+ */
+function rethrow(error: unknown): never {
+  if (error instanceof Error && 'kind' in error) {
+    throw new RicoError(error as unknown as ParseError);
+  }
+  if (typeof error === 'string') {
+    let details: unknown;
+    try {
+      details = JSON.parse(error);
+    } catch {
+      throw error;
+    }
+    if (details && typeof details === 'object' && 'kind' in details) {
+      throw new RicoError(details as ParseError);
+    }
+  }
+  throw error;
+}
+
 export class Rico {
   private static initialized = false;
 
@@ -40,17 +64,13 @@ export class Rico {
     const parser = new Parser(input);
     toString = typeof toString === 'undefined' ? true : toString;
     try {
-      const result = parser.parse();
-      return Promise.resolve(!toString ? JSON.parse(result) : result);
+      // The object API skips the JSON round trip through a string
+      const result = toString ? parser.parse() : parser.parseObject();
+      return Promise.resolve(result);
     } catch (error) {
-      try {
-        const parserError = JSON.parse(error as string);
-        if (parserError && 'kind' in parserError) {
-          throw new RicoError(error as ParseError);
-        }
-      } finally {
-        throw error;
-      }
+      rethrow(error);
+    } finally {
+      parser.free();
     }
   }
 
@@ -60,19 +80,26 @@ export class Rico {
     }
     const writer = new Writer();
     try {
-      return writer.write(JSON.stringify(ast));
+      return writer.writeObject(ast);
+    } catch (error) {
+      rethrow(error);
+    } finally {
+      writer.free();
+    }
+  }
+
+  static format(source: string, options?: FormatOptions): string {
+    if (!Rico.initialized) {
+      throw new Error('Rico is not initialized. Call Rico.initialize() first.');
+    }
+    try {
+      return format(source, options);
     } catch (error) {
-      try {
-        const parserError = JSON.parse(error as string);
-        if (parserError && 'kind' in parserError) {
-          throw new RicoError(error as ParseError);
-        }
-      } finally {
-        throw error;
-      }
+      rethrow(error);
     }
   }
 }
 
+export { Workspace } from './wasm/rico_wasm';
 export * from './types';
 export default Rico;
@@ -1,5 +1,9 @@
+#[cfg(test)]
+mod declarations;
 mod error;
 mod utils;
 mod wasm;
+mod workspace;
 
 pub use wasm::*;
+pub use workspace::*;
@@ -1,233 +1,109 @@
-export type NodeType =
-  | 'ThriftDocument'
-  | 'NamespaceDefinition'
-  | 'IncludeDefinition'
-  | 'ConstDefinition'
-  | 'TypedefDefinition'
-  | 'EnumDefinition'
-  | 'StructDefinition'
-  | 'UnionDefinition'
-  | 'ExceptionDefinition'
-  | 'ServiceDefinition'
-  | 'FunctionDefinition'
-  | 'FieldDefinition'
-  | 'CommentLine'
-  | 'CommentBlock'
-  | 'Annotation'
-  | 'Annotations'
-  | 'CommonType'
-  | 'CollectionType'
-  | 'MapType'
-  | 'ConstValue'
-  | 'ConstList'
-  | 'ConstMap'
-  | 'PropertyAssignment';
-
-export interface Span {
-  line: number;
-  column: number;
-  index: number;
-}
-
-export interface LOC {
-  start: Span;
-  end: Span;
-}
-
-export interface Common<T = string> {
-  kind: NodeType;
-  value: T;
-  loc: LOC;
-}
-
-export interface Document {
-  kind: 'ThriftDocument';
-  members: DocumentMember[];
-}
-
-export type DocumentMember =
-  | Namespace
-  | Include
-  | Const
-  | Typedef
-  | Enum
-  | Struct
-  | Union
-  | Exception
-  | Service;
+// The AST types are generated from the Rust definitions and emitted into the
+// declarations of the WASM module. This file re-exports them, and adds the
+// names of earlier versions of this package.
+import type {
+  Annotations,
+  Comment,
+  DocumentMembers,
+  FieldDefinition,
+  FieldInitialValue,
+  ListType,
+  LOC,
+  MapType,
+  NodeType,
+  PropertyAssignment,
+  RicoError as RicoErrorDetails,
+  SetType,
+  ThriftDocument
+} from './wasm/rico_wasm';
+
+export type {
+  Annotation,
+  Annotations,
+  Comment,
+  Common,
+  ConstList,
+  ConstMap,
+  DocumentMembers,
+  EnumMember,
+  FieldDefinition,
+  FieldInitialValue,
+  FieldType,
+  FormatOptions,
+  FormatSeparator,
+  FunctionDefinition,
+  Initializer,
+  ListType,
+  LOC,
+  Location,
+  MapType,
+  NodeType,
+  PositionEncoding,
+  PropertyAssignment,
+  SetType,
+  Span,
+  ThriftDocument,
+  FileDiagnostics,
+  ParsedFile,
+  WorkspaceParseResult,
+  WorkspaceValidationResult
+} from './wasm/rico_wasm';
+
+/** A top-level definition with the given `kind` */
+type Member<K extends DocumentMembers['kind']> = Extract<
+  DocumentMembers,
+  { kind: K }
+>;
+
+export type Namespace = Member<'NamespaceDefinition'>;
+export type Include = Member<'IncludeDefinition'>;
+export type Const = Member<'ConstDefinition'>;
+export type Typedef = Member<'TypedefDefinition'>;
+export type Enum = Member<'EnumDefinition'>;
+export type Struct = Member<'StructDefinition'>;
+export type Union = Member<'UnionDefinition'>;
+export type Exception = Member<'ExceptionDefinition'>;
+export type Service = Member<'ServiceDefinition'>;
+
+export type DocumentMember = DocumentMembers;
+
+// The generated declarations are named after the `kind` tag of each node
+export type Document = ThriftDocument;
+export type Field = FieldDefinition;
+export type FieldListType = ListType;
+export type FieldSetType = SetType;
+export type FieldMapType = MapType;
+export type MapProperty = PropertyAssignment;
 
 export interface BaseNode {
   kind: NodeType;
   loc: LOC;
   comments: Comment[];
-  annotations?: Annotations;
-}
-
-export interface Comment {
-  kind: 'CommentLine' | 'CommentBlock';
-  value: string;
-  loc: LOC;
-}
-
-export interface Annotation {
-  kind: 'Annotation';
-  name: Common<string>;
-  value: Common<string>;
-  loc: LOC;
+  annotations?: Annotations | null;
 }
 
-export interface Annotations {
-  kind: 'Annotations';
-  loc: LOC;
-  members: Annotation[];
-}
-
-export interface Namespace extends BaseNode {
-  kind: 'NamespaceDefinition';
-  scope: Common<string>;
-  name: Common<string>;
-}
-
-export interface Include extends BaseNode {
-  kind: 'IncludeDefinition';
-  name: Common<string>;
-}
-
-export interface Const extends BaseNode {
-  kind: 'ConstDefinition';
-  name: Common<string>;
-  fieldType: FieldType;
-  value: FieldValue;
-}
-
-export interface Typedef extends BaseNode {
-  kind: 'TypedefDefinition';
-  name: Common<string>;
-  fieldType: FieldType;
-}
-
-export interface Initializer {
-  kind: NodeType;
-  value: Common<string>;
-  loc: LOC;
-}
-
-export interface EnumMember extends BaseNode {
-  kind: NodeType;
-  name: Common<string>;
-  initializer?: Initializer;
-}
-
-export interface Enum extends BaseNode {
-  kind: 'EnumDefinition';
-  name: Common<string>;
-  members: EnumMember[];
-}
-
-export interface Field extends BaseNode {
-  kind: 'FieldDefinition';
-  name: Common<string>;
-  fieldID?: Common<string>;
-  fieldType: FieldType;
-  requiredType: string;
-  defaultValue?: FieldValue;
-}
-
-export interface Struct extends BaseNode {
-  kind: 'StructDefinition';
-  name: Common<string>;
-  members: Field[];
-}
-
-export interface Union extends BaseNode {
-  kind: 'UnionDefinition';
-  name: Common<string>;
-  members: Field[];
-}
-
-export interface Exception extends BaseNode {
-  kind: 'ExceptionDefinition';
-  name: Common<string>;
-  members: Field[];
-}
-
-export interface Function extends BaseNode {
-  kind: 'FunctionDefinition';
-  name: Common<string>;
-  returnType: FieldType;
-  params: Field[];
-  throws?: Field[];
-  oneway: boolean;
-}
-
-export interface Service extends BaseNode {
-  kind: 'ServiceDefinition';
-  name: Common<string>;
-  extends?: Common<string>;
-  members: Function[];
-}
-
-export interface FieldListType {
-  kind: 'ListType';
-  loc: LOC;
-  value: string;
-  valueType: FieldType;
-}
-
-export interface FieldSetType {
-  kind: 'SetType';
-  loc: LOC;
-  value: string;
-  valueType: FieldType;
-}
-
-export interface FieldMapType {
-  kind: 'MapType';
-  loc: LOC;
-  value: string;
-  valueType: FieldType;
-  keyType: FieldType;
-}
-
-export type FieldType =
-  | { kind: 'CommonType'; value: string; loc: LOC }
-  | FieldListType
-  | FieldSetType
-  | FieldMapType;
-
-export interface ConstList {
-  kind: 'ConstList';
-  loc: LOC;
-  elements: FieldValue[];
-}
-
-export interface MapProperty {
-  kind: 'PropertyAssignment';
-  loc: LOC;
-  name: FieldValue;
-  value: FieldValue;
-}
-
-export interface ConstMap {
-  kind: 'ConstMap';
-  loc: LOC;
-  properties: MapProperty[];
-}
+export type FieldValue = FieldInitialValue;
 
-export type FieldValue =
-  | Common<string | number | boolean>
-  | ConstList
-  | ConstMap;
+/** The error fields thrown by the WASM module, discriminated by `kind` */
+export type { RicoErrorDetails };
 
 export interface ParseError {
-  kind: 'ParseError' | 'SerializationError' | 'DeserializationError';
+  kind:
+    | 'ParseError'
+    | 'ParseWarning'
+    | 'IncludeError'
+    | 'WriteError'
+    | 'SerializationError'
+    | 'DeserializationError';
   message: string;
   code: string;
-  help?: string;
+  help?: string | null;
+  /** JSON path of the offending node, only set for `WriteError` */
+  path?: string | null;
   location?: {
     line: number;
     column: number;
     length: number;
     sourceText: string;
-  };
+  } | null;
 }
@@ -1,13 +1,111 @@
-use rico::{Parser as RicoParser, Writer as RicoWriter};
+use rico::line_index::PositionEncoding;
+use rico::writer::{Indent, Separator, WriterOptions};
+use rico::{Document, Parser as RicoParser, Writer as RicoWriter};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::Serializer;
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use crate::error::RicoError;
 use crate::utils::set_panic_hook;
 use serde_json;
 
+/// The AST as a JS object, declared as `ThriftDocument` in TypeScript
+type DocumentObject = <Document as Tsify>::JsType;
+
+/// An error or warning as a JS object, declared as `RicoError` in TypeScript
+type RicoErrorObject = <RicoError as Tsify>::JsType;
+
+/// A position encoding as a JS string, e.g. `"utf-16"`
+pub(crate) type PositionEncodingValue = <PositionEncoding as Tsify>::JsType;
+
+/// Columns count UTF-8 bytes by default, as in earlier versions, so existing
+/// callers keep the same AST locations; JS callers opt into `utf-16`
+pub(crate) const DEFAULT_POSITION_ENCODING: PositionEncoding = PositionEncoding::Utf8;
+
+/// Options of `format`, named after the Prettier options where they overlap
+#[derive(Deserialize, Tsify, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatOptions {
+    /// Maximum line width, unlimited if not set
+    #[tsify(optional)]
+    print_width: Option<usize>,
+    /// Whether broken lists end with a trailing comma
+    trailing_comma: bool,
+    /// Whether to sort and normalize the output, see `WriterOptions::canonical`
+    canonical: bool,
+    /// Spaces per indentation level, 2 if not set
+    #[tsify(optional)]
+    tab_width: Option<usize>,
+    /// Whether to indent with tabs instead of spaces
+    use_tabs: bool,
+    /// Separator after struct fields and enum members, `comma` if not set
+    #[tsify(optional)]
+    separator: Option<FormatSeparator>,
+    /// Unit of the columns of error locations, `utf-8` if not set
+    #[tsify(optional)]
+    position_encoding: Option<PositionEncoding>,
+}
+
+#[derive(Deserialize, Tsify, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatSeparator {
+    Comma,
+    Semicolon,
+    None,
+}
+
+impl From<FormatOptions> for WriterOptions {
+    fn from(options: FormatOptions) -> Self {
+        let indent = if options.use_tabs {
+            Indent::Tabs
+        } else {
+            options
+                .tab_width
+                .map_or_else(Indent::default, Indent::Spaces)
+        };
+        let separator = match options.separator {
+            Some(FormatSeparator::Comma) | None => Separator::Comma,
+            Some(FormatSeparator::Semicolon) => Separator::Semicolon,
+            Some(FormatSeparator::None) => Separator::None,
+        };
+        WriterOptions {
+            max_width: options.print_width,
+            trailing_comma: options.trailing_comma,
+            canonical: options.canonical,
+            indent,
+            separator,
+        }
+    }
+}
+
+/// Formats Thrift IDL source with the given options
+/// Throws an `Error` carrying the `RicoError` fields if the source cannot be
+/// parsed or the options are invalid
+#[wasm_bindgen]
+pub fn format(
+    source: &str,
+    options: Option<<FormatOptions as Tsify>::JsType>,
+) -> Result<String, JsValue> {
+    let options: FormatOptions = match options {
+        Some(options) => from_js(options.into())?,
+        None => FormatOptions::default(),
+    };
+    let encoding = options
+        .position_encoding
+        .unwrap_or(DEFAULT_POSITION_ENCODING);
+    rico::format::format(source, options.into())
+        .map_err(|e| RicoError::parse(e, source, encoding).into_js())
+}
+
 #[wasm_bindgen]
 pub struct Parser {
     input: String,
+    position_encoding: PositionEncoding,
+    strict: bool,
+    /// Warnings of the last parse
+    warnings: Vec<RicoError>,
 }
 
 #[wasm_bindgen]
@@ -15,24 +113,87 @@ impl Parser {
     #[wasm_bindgen(constructor)]
     pub fn new(input: String) -> Self {
         set_panic_hook();
-        Self { input }
+        Self {
+            input,
+            position_encoding: DEFAULT_POSITION_ENCODING,
+            strict: false,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Sets the unit of the columns of AST and error locations
+    /// Defaults to `utf-8`; use `utf-16` for columns that match JS string indices
+    #[wasm_bindgen(js_name = setPositionEncoding)]
+    pub fn set_position_encoding(
+        &mut self,
+        encoding: PositionEncodingValue,
+    ) -> Result<(), JsValue> {
+        self.position_encoding = from_js(encoding.into())?;
+        Ok(())
+    }
+
+    /// Enables strict mode, in which keywords used as names, such as
+    /// `1: binary binary`, are reported by `warnings()`
+    #[wasm_bindgen(js_name = setStrict)]
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns the warnings of the last parse, such as invalid escape
+    /// sequences, and keywords used as names in strict mode
+    #[wasm_bindgen]
+    pub fn warnings(&self) -> Result<Vec<RicoErrorObject>, JsValue> {
+        self.warnings
+            .iter()
+            .map(|warning| Ok(to_js(warning)?.unchecked_into()))
+            .collect()
     }
 
     #[wasm_bindgen]
     pub fn parse(&mut self) -> Result<String, String> {
-        let mut parser = RicoParser::new(&self.input);
-        match parser.parse() {
-            Ok(ast) => {
-                let value = serde_json::to_string(&ast)
-                    .map_err(|e| RicoError::serialization(e).to_string());
-
-                return value;
-            }
-            Err(e) => Err(RicoError::parse(e, &self.input).to_string()),
-        }
+        let ast = self.parse_document().map_err(|e| e.to_string())?;
+        serde_json::to_string(&ast).map_err(|e| RicoError::serialization(e).to_string())
+    }
+
+    /// Parses the input and returns the AST as a JS object
+    /// Throws an `Error` carrying the `RicoError` fields on failure
+    #[wasm_bindgen(js_name = parseObject)]
+    pub fn parse_object(&mut self) -> Result<DocumentObject, JsValue> {
+        let ast = self.parse_document().map_err(RicoError::into_js)?;
+        Ok(to_js(&ast)?.unchecked_into())
     }
 }
 
+impl Parser {
+    /// Parses the input and keeps its warnings
+    fn parse_document(&mut self) -> Result<Document, RicoError> {
+        let mut parser = RicoParser::new(&self.input)
+            .with_position_encoding(self.position_encoding)
+            .with_strict(self.strict);
+        let result = parser
+            .parse()
+            .map_err(|e| RicoError::parse(e, &self.input, self.position_encoding));
+        self.warnings = parser
+            .warnings()
+            .iter()
+            .map(|warning| RicoError::warning(warning, &self.input, self.position_encoding))
+            .collect();
+        result
+    }
+}
+
+/// Converts a value into a JS object with the same shape as its JSON form
+pub(crate) fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&Serializer::json_compatible())
+        .map_err(|e| RicoError::serialization(e).into_js())
+}
+
+/// Converts a JS value into a Rust value with the same shape
+pub(crate) fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| RicoError::deserialization(e).into_js())
+}
+
 #[wasm_bindgen]
 pub struct Writer {
     inner: RicoWriter,
@@ -53,8 +214,59 @@ impl Writer {
         let ast: rico::Document =
             serde_json::from_str(&ast).map_err(|e| RicoError::deserialization(e).to_string())?;
 
-        let result = self.inner.write(&ast);
+        let result = self
+            .inner
+            .try_write(&ast)
+            .map_err(|e| RicoError::write(e).to_string())?;
 
         Ok(result)
     }
+
+    /// Writes an AST given as a JS object back to Thrift IDL
+    /// Throws an `Error` carrying the `RicoError` fields on failure
+    #[wasm_bindgen(js_name = writeObject)]
+    pub fn write_object(&mut self, ast: DocumentObject) -> Result<String, JsValue> {
+        let ast: Document = from_js(ast.into())?;
+
+        self.inner
+            .try_write(&ast)
+            .map_err(|e| RicoError::write(e).into_js())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the codes of the warnings of the last parse
+    fn codes(parser: &Parser) -> Vec<&str> {
+        parser
+            .warnings
+            .iter()
+            .map(|warning| match warning {
+                RicoError::Warning { code, .. } => code.as_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_warnings() {
+        let mut parser = Parser::new(
+            r#"struct Blob { 1: binary binary (go.tag = "regexp=^\d+$") }"#.to_string(),
+        );
+        assert!(parser.parse_document().is_ok());
+        assert_eq!(codes(&parser), ["rico::parser::invalid_escape"]);
+
+        // Strict mode adds warnings, it does not fail parsing
+        parser.set_strict(true);
+        assert!(parser.parse_document().is_ok());
+        assert_eq!(
+            codes(&parser),
+            [
+                "rico::parser::keyword_as_name",
+                "rico::parser::invalid_escape"
+            ]
+        );
+    }
 }
@@ -0,0 +1,422 @@
+//! A virtual file system for parsing Thrift files that include each other.
+//!
+//! JS adds files with their path and content, so includes are resolved
+//! against the workspace instead of the file system. Paths use `/` as the
+//! separator and are normalized, so `./a/../b.thrift` and `b.thrift` name
+//! the same file. Paths that leave the workspace root with `..` are rejected.
+
+use std::collections::BTreeMap;
+
+use rico::line_index::PositionEncoding;
+use rico::{Document, DocumentMembers, Parser as RicoParser};
+use serde::Serialize;
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+use crate::error::RicoError;
+use crate::utils::set_panic_hook;
+use crate::wasm::{from_js, to_js, PositionEncodingValue, DEFAULT_POSITION_ENCODING};
+
+/// The result of parsing a file of the workspace
+#[derive(Serialize, Tsify)]
+pub struct ParsedFile {
+    /// Normalized path of the file
+    path: String,
+    /// The AST, unless the file failed to parse
+    #[tsify(type = "ThriftDocument | null")]
+    document: Option<Document>,
+    /// Workspace paths of the resolved includes, keyed by include path
+    includes: BTreeMap<String, String>,
+    /// Parse errors and unresolved includes of the file
+    diagnostics: Vec<RicoError>,
+    /// Parser warnings of the file, which do not make it invalid
+    warnings: Vec<RicoError>,
+}
+
+/// The diagnostics of a file of the workspace
+#[derive(Serialize, Tsify)]
+pub struct FileDiagnostics {
+    path: String,
+    diagnostics: Vec<RicoError>,
+    warnings: Vec<RicoError>,
+}
+
+/// The result of `Workspace.parse()`
+#[derive(Serialize, Tsify)]
+pub struct WorkspaceParseResult {
+    files: Vec<ParsedFile>,
+}
+
+/// The result of `Workspace.validate()`
+#[derive(Serialize, Tsify)]
+pub struct WorkspaceValidationResult {
+    /// Whether no file has diagnostics
+    valid: bool,
+    files: Vec<FileDiagnostics>,
+}
+
+#[wasm_bindgen]
+pub struct Workspace {
+    /// File contents keyed by normalized path
+    files: BTreeMap<String, String>,
+    position_encoding: PositionEncoding,
+    strict: bool,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            files: BTreeMap::new(),
+            position_encoding: DEFAULT_POSITION_ENCODING,
+            strict: false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Workspace {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        set_panic_hook();
+        Self::default()
+    }
+
+    /// Sets the unit of the columns of AST and diagnostic locations
+    /// Defaults to `utf-8`; use `utf-16` for columns that match JS string indices
+    #[wasm_bindgen(js_name = setPositionEncoding)]
+    pub fn set_position_encoding(
+        &mut self,
+        encoding: PositionEncodingValue,
+    ) -> Result<(), JsValue> {
+        self.position_encoding = from_js(encoding.into())?;
+        Ok(())
+    }
+
+    /// Enables strict mode, in which keywords used as names, such as
+    /// `1: binary binary`, are reported as warnings
+    #[wasm_bindgen(js_name = setStrict)]
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Adds a file, replacing the content of a file with the same path
+    /// Throws if the path leaves the workspace root
+    #[wasm_bindgen(js_name = addFile)]
+    pub fn add_file(&mut self, path: &str, content: String) -> Result<(), JsValue> {
+        self.insert(path, content).map_err(RicoError::into_js)
+    }
+
+    /// Removes a file, returning whether it was part of the workspace
+    #[wasm_bindgen(js_name = removeFile)]
+    pub fn remove_file(&mut self, path: &str) -> bool {
+        normalize(path).is_some_and(|path| self.files.remove(&path).is_some())
+    }
+
+    /// Returns the normalized paths of all files, sorted
+    #[wasm_bindgen]
+    pub fn files(&self) -> Vec<String> {
+        self.files.keys().cloned().collect()
+    }
+
+    /// Removes all files
+    #[wasm_bindgen]
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+
+    /// Parses every file and resolves its includes
+    #[wasm_bindgen]
+    pub fn parse(&self) -> Result<<WorkspaceParseResult as Tsify>::JsType, JsValue> {
+        let result = WorkspaceParseResult {
+            files: self.parse_files(),
+        };
+        Ok(to_js(&result)?.unchecked_into())
+    }
+
+    /// Parses every file and reports its diagnostics without the ASTs
+    #[wasm_bindgen]
+    pub fn validate(&self) -> Result<<WorkspaceValidationResult as Tsify>::JsType, JsValue> {
+        let files: Vec<FileDiagnostics> = self
+            .parse_files()
+            .into_iter()
+            .map(|file| FileDiagnostics {
+                path: file.path,
+                diagnostics: file.diagnostics,
+                warnings: file.warnings,
+            })
+            .collect();
+        let result = WorkspaceValidationResult {
+            valid: files.iter().all(|file| file.diagnostics.is_empty()),
+            files,
+        };
+        Ok(to_js(&result)?.unchecked_into())
+    }
+}
+
+impl Workspace {
+    fn insert(&mut self, path: &str, content: String) -> Result<(), RicoError> {
+        let normalized = normalize(path).ok_or_else(|| RicoError::invalid_path(path))?;
+        self.files.insert(normalized, content);
+        Ok(())
+    }
+
+    fn parse_files(&self) -> Vec<ParsedFile> {
+        self.files
+            .iter()
+            .map(|(path, content)| self.parse_file(path, content))
+            .collect()
+    }
+
+    fn parse_file(&self, path: &str, content: &str) -> ParsedFile {
+        let mut parser = RicoParser::new(content)
+            .with_position_encoding(self.position_encoding)
+            .with_strict(self.strict);
+        let document = match parser.parse() {
+            Ok(document) => document,
+            Err(e) => {
+                return ParsedFile {
+                    path: path.to_string(),
+                    document: None,
+                    includes: BTreeMap::new(),
+                    diagnostics: vec![RicoError::parse(e, content, self.position_encoding)],
+                    warnings: Vec::new(),
+                }
+            }
+        };
+
+        let warnings = parser
+            .warnings()
+            .iter()
+            .map(|warning| RicoError::warning(warning, content, self.position_encoding))
+            .collect();
+        let mut includes = BTreeMap::new();
+        let mut diagnostics = Vec::new();
+        for member in &document.members {
+            let DocumentMembers::Include(include) = member else {
+                continue;
+            };
+            let name = include.name.text();
+            match self.resolve(path, &name) {
+                Some(resolved) => {
+                    includes.insert(name.to_string(), resolved);
+                }
+                None => {
+                    let loc = &include.name.loc;
+                    diagnostics.push(RicoError::unresolved_include(
+                        &name,
+                        loc.start.index,
+                        loc.end.index - loc.start.index,
+                        content,
+                        self.position_encoding,
+                    ));
+                }
+            }
+        }
+
+        ParsedFile {
+            path: path.to_string(),
+            document: Some(document),
+            includes,
+            diagnostics,
+            warnings,
+        }
+    }
+
+    /// Resolves an include relative to the including file, then to the
+    /// workspace root
+    /// Includes that leave the workspace root are not resolved
+    fn resolve(&self, from: &str, include: &str) -> Option<String> {
+        let dir = from.rsplit_once('/').map_or("", |(dir, _)| dir);
+        [
+            normalize(&format!("{}/{}", dir, include)),
+            normalize(include),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|path| self.files.contains_key(path))
+    }
+}
+
+/// Normalizes a path to `/` separators without `.` and `..` segments
+/// Returns `None` if a `..` segment leaves the workspace root
+fn normalize(path: &str) -> Option<String> {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(files: &[(&str, &str)]) -> Workspace {
+        let mut workspace = Workspace::default();
+        for (path, content) in files {
+            workspace.insert(path, content.to_string()).unwrap();
+        }
+        workspace
+    }
+
+    /// Returns the diagnostic codes of a file
+    fn codes(file: &ParsedFile) -> Vec<&str> {
+        file.diagnostics
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                RicoError::Parse { code, .. } | RicoError::Include { code, .. } => code.as_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn warning_codes(file: &ParsedFile) -> Vec<&str> {
+        file.warnings
+            .iter()
+            .map(|warning| match warning {
+                RicoError::Warning { code, .. } => code.as_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a/b.thrift").as_deref(), Some("a/b.thrift"));
+        assert_eq!(normalize("./a/../b.thrift").as_deref(), Some("b.thrift"));
+        assert_eq!(normalize("/a//./b.thrift").as_deref(), Some("a/b.thrift"));
+        assert_eq!(normalize(r"a\b\..\c.thrift").as_deref(), Some("a/c.thrift"));
+        assert_eq!(normalize("a/..").as_deref(), Some(""));
+        assert_eq!(normalize("../a.thrift"), None);
+        assert_eq!(normalize("a/../../a.thrift"), None);
+    }
+
+    #[test]
+    fn test_paths_outside_the_root() {
+        let mut workspace = Workspace::default();
+        let Err(RicoError::Include { code, .. }) = workspace.insert("../a.thrift", String::new())
+        else {
+            panic!("a path outside the root was accepted");
+        };
+        assert_eq!(code, "INVALID_PATH");
+        assert!(workspace.files().is_empty());
+        assert!(!workspace.remove_file("../a.thrift"));
+
+        workspace.insert("./a/../b.thrift", String::new()).unwrap();
+        assert_eq!(workspace.files(), ["b.thrift"]);
+        assert!(workspace.remove_file("b.thrift"));
+    }
+
+    #[test]
+    fn test_resolve_includes() {
+        let workspace = workspace(&[
+            ("a.thrift", ""),
+            ("common/types.thrift", ""),
+            ("api/common/types.thrift", ""),
+            ("api/service.thrift", ""),
+        ]);
+
+        // Relative to the including file first, then to the root
+        assert_eq!(
+            workspace.resolve("api/service.thrift", "common/types.thrift"),
+            Some("api/common/types.thrift".to_string())
+        );
+        assert_eq!(
+            workspace.resolve("common/types.thrift", "a.thrift"),
+            Some("a.thrift".to_string())
+        );
+        assert_eq!(
+            workspace.resolve("api/service.thrift", "../common/types.thrift"),
+            Some("common/types.thrift".to_string())
+        );
+        assert_eq!(
+            workspace.resolve("api/service.thrift", "./../a.thrift"),
+            Some("a.thrift".to_string())
+        );
+
+        // Missing files and paths that leave the root are not resolved
+        assert_eq!(
+            workspace.resolve("api/service.thrift", "missing.thrift"),
+            None
+        );
+        assert_eq!(workspace.resolve("a.thrift", "../a.thrift"), None);
+        assert_eq!(
+            workspace.resolve("api/service.thrift", "../../a.thrift"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_files() {
+        let workspace = workspace(&[
+            ("common/types.thrift", "struct User { 1: string name }"),
+            (
+                "api/service.thrift",
+                "include \"../common/types.thrift\"\ninclude \"../../outside.thrift\"\ninclude 'missing.thrift'\ninclude 'common\\/types.thrift'",
+            ),
+            ("broken.thrift", "struct {"),
+        ]);
+
+        let files = workspace.parse_files();
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["api/service.thrift", "broken.thrift", "common/types.thrift"]
+        );
+
+        let service = &files[0];
+        assert!(service.document.is_some());
+        assert_eq!(
+            service.includes,
+            BTreeMap::from([
+                (
+                    "../common/types.thrift".to_string(),
+                    "common/types.thrift".to_string()
+                ),
+                // Resolved with the decoded path `common/types.thrift`
+                (
+                    "common/types.thrift".to_string(),
+                    "common/types.thrift".to_string()
+                ),
+            ])
+        );
+        assert_eq!(codes(service), ["UNRESOLVED_INCLUDE", "UNRESOLVED_INCLUDE"]);
+
+        let broken = &files[1];
+        assert!(broken.document.is_none());
+        assert_eq!(codes(broken), ["rico::parser::missing_struct_identifier"]);
+
+        let types = &files[2];
+        assert!(types.document.is_some());
+        assert!(codes(types).is_empty());
+    }
+
+    #[test]
+    fn test_parse_files_strict() {
+        let mut workspace = workspace(&[(
+            "a.thrift",
+            r#"struct Blob { 1: binary binary (go.tag = "regexp=^\d+$") }"#,
+        )]);
+        let files = workspace.parse_files();
+        assert!(codes(&files[0]).is_empty());
+        assert_eq!(warning_codes(&files[0]), ["rico::parser::invalid_escape"]);
+
+        workspace.set_strict(true);
+        let files = workspace.parse_files();
+        assert!(files[0].document.is_some());
+        assert!(codes(&files[0]).is_empty());
+        assert_eq!(
+            warning_codes(&files[0]),
+            [
+                "rico::parser::keyword_as_name",
+                "rico::parser::invalid_escape"
+            ]
+        );
+    }
+}